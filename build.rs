@@ -27,7 +27,41 @@ fn copy_dir<P, Q>(from: P, to: Q) where P: AsRef<Path>, Q: AsRef<Path> {
     }
 }
 
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    for path in fs::read_dir(dir).unwrap() {
+        let path: PathBuf = path.unwrap().path();
+
+        if path.is_file() {
+            files.push(path);
+        } else if path.is_dir() {
+            collect_files(&path, files);
+        }
+    }
+}
+
+fn write_embedded_assets() {
+    // Generates list of all asset files, which is included into the binary with "embedded-assets" feature
+    let assets_dir: PathBuf = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join("assets");
+    let mut files: Vec<PathBuf> = Vec::new();
+    collect_files(&assets_dir, &mut files);
+    files.sort();
+
+    let mut code: String = String::from("static EMBEDDED_ASSETS: &[(&str, &[u8])] = &[\n");
+    for file in files.iter() {
+        let relative_path: String = file.strip_prefix(&assets_dir).unwrap().to_string_lossy().replace('\\', "/");
+        code.push_str(&format!("    ({:?}, include_bytes!({:?})),\n", relative_path, file.to_string_lossy()));
+    }
+    code.push_str("];\n");
+
+    let out: PathBuf = PathBuf::from(env::var("OUT_DIR").unwrap()).join("embedded_assets.rs");
+    fs::write(out, code).unwrap();
+}
+
 fn main() {
+    if env::var("CARGO_FEATURE_EMBEDDED_ASSETS").is_ok() {
+        write_embedded_assets();
+    }
+
     for directory in COPY_DIRS {
         let out  = PathBuf::from(format!("target/{}/{}", env::var("PROFILE").unwrap(), directory));
 
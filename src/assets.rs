@@ -0,0 +1,76 @@
+use raylib::prelude::*;
+use std::path::{ Path, PathBuf };
+
+use crate::paths;
+
+#[cfg(feature = "embedded-assets")]
+include!(concat!(env!("OUT_DIR"), "/embedded_assets.rs"));
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AssetError {
+    NotFound { path: String, details: String },
+    Invalid { path: String, details: String },
+}
+
+impl AssetError {
+    pub fn not_found(path: &str, details: String) -> Self {
+        Self::NotFound { path: path.to_string(), details: details }
+    }
+
+    pub fn invalid(path: &str, details: String) -> Self {
+        Self::Invalid { path: path.to_string(), details: details }
+    }
+}
+
+impl std::fmt::Display for AssetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::NotFound { path, details } => write!(f, "Unable to read {}: {}", path, details),
+            Self::Invalid { path, details } => write!(f, "Unable to load {}: {}", path, details),
+        }
+    }
+}
+
+pub fn read(relative_path: &str) -> Result<Vec<u8>, AssetError> {
+    // File on disk takes precedence over the embedded one, so assets can be replaced without rebuilding the game
+    let path: PathBuf = paths::get_asset_path(relative_path);
+    match std::fs::read(&path) {
+        Ok(data) => Ok(data),
+        Err(e) => match read_embedded(relative_path) {
+            Some(data) => Ok(data.to_vec()),
+            None => Err(AssetError::not_found(&path.display().to_string(), e.to_string())),
+        },
+    }
+}
+
+pub fn read_to_string(relative_path: &str) -> Result<String, AssetError> {
+    String::from_utf8(read(relative_path)?).map_err(|e| AssetError::invalid(relative_path, e.to_string()))
+}
+
+pub fn load_image(relative_path: &str) -> Result<Image, AssetError> {
+    let data: Vec<u8> = read(relative_path)?;
+    Image::load_image_from_mem(&get_file_type(relative_path), &data).map_err(|e| AssetError::invalid(relative_path, e.to_string()))
+}
+
+pub fn load_font(rl: &mut RaylibHandle, thread: &RaylibThread, relative_path: &str, font_size: i32, chars: &str) -> Result<Font, AssetError> {
+    let data: Vec<u8> = read(relative_path)?;
+    rl.load_font_from_memory(thread, &get_file_type(relative_path), &data, font_size, Some(chars)).map_err(|e| AssetError::invalid(relative_path, e.to_string()))
+}
+
+fn get_file_type(relative_path: &str) -> String {
+    // raylib expects file type with a leading dot, e.g. ".png"
+    match Path::new(relative_path).extension() {
+        Some(extension) => format!(".{}", extension.to_string_lossy()),
+        None => String::new(),
+    }
+}
+
+#[cfg(feature = "embedded-assets")]
+fn read_embedded(relative_path: &str) -> Option<&'static [u8]> {
+    EMBEDDED_ASSETS.iter().find(|(path, _)| *path == relative_path).map(|(_, data)| *data)
+}
+
+#[cfg(not(feature = "embedded-assets"))]
+fn read_embedded(_relative_path: &str) -> Option<&'static [u8]> {
+    None
+}
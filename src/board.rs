@@ -0,0 +1,500 @@
+use crate::rng::SeededRng;
+use crate::sequence::SequenceRule;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoardSize {
+    pub columns: i32,
+    pub rows: i32,
+}
+
+impl std::fmt::Display for BoardSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}x{}", self.columns, self.rows)
+    }
+}
+
+impl std::str::FromStr for BoardSize {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (columns, rows) = value.trim().split_once("x").ok_or_else(|| format!("Invalid board size: {}", value))?;
+        let size: Self = Self::new(
+            columns.trim().parse::<i32>().map_err(|e| e.to_string())?, 
+            rows.trim().parse::<i32>().map_err(|e| e.to_string())?, 
+        );
+
+        if size.is_valid() { Ok(size) } else { Err(format!("Board size out of range: {}", value)) }
+    }
+}
+
+impl BoardSize {
+    pub const MIN_SIDE: i32 = 5;
+    pub const MAX_SIDE: i32 = 10;
+    pub const PRESETS: [BoardSize; 7] = [
+        BoardSize::new(5, 5),
+        BoardSize::new(6, 6),
+        BoardSize::new(7, 7),
+        BoardSize::new(8, 7),
+        BoardSize::new(8, 8),
+        BoardSize::new(9, 9),
+        BoardSize::new(10, 10),
+    ];
+
+    pub const fn new(columns: i32, rows: i32) -> Self {
+        Self {
+            columns: columns,
+            rows: rows,
+        }
+    }
+
+    pub fn count(&self) -> i32 {
+        self.columns * self.rows
+    }
+
+    pub fn is_valid(&self) -> bool {
+        (Self::MIN_SIDE..=Self::MAX_SIDE).contains(&self.columns) && (Self::MIN_SIDE..=Self::MAX_SIDE).contains(&self.rows)
+    }
+
+    // Index of the neighbouring tile of a row-major board, the cursor stops at the edges
+    pub fn move_index(&self, index: usize, columns_delta: i32, rows_delta: i32) -> usize {
+        let column: i32 = (index as i32 % self.columns + columns_delta).clamp(0, self.columns - 1);
+        let row: i32 = (index as i32 / self.columns + rows_delta).clamp(0, self.rows - 1);
+        (row * self.columns + column) as usize
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TileColor {
+    Black,
+    Red,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tile {
+    pub number: i32,
+    pub color: TileColor,
+}
+
+impl Tile {
+    pub const fn black(number: i32) -> Self {
+        Self {
+            number: number,
+            color: TileColor::Black,
+        }
+    }
+
+    pub const fn red(number: i32) -> Self {
+        Self {
+            number: number,
+            color: TileColor::Red,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ClickResult {
+    Correct,
+    Wrong,
+    RepeatedWrong,
+    AlreadyCleared,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Outcome {
+    Win,
+    Lose,
+}
+
+// Board state and game rules without any raylib dependency, so it can be driven by Level
+// and checked by unit tests without opening a window
+#[derive(Clone, Debug, Default)]
+pub struct Board {
+    tiles: Vec<Tile>,
+    sequence: Vec<Tile>,
+    seed: u64,
+    correct_tiles: Vec<usize>,
+    incorrect_tile: Option<usize>,
+    score: i32,
+    fails: i32,
+}
+
+impl Board {
+    pub fn new(numbers: Vec<i32>) -> Self {
+        let mut sequence: Vec<i32> = numbers.clone();
+        sequence.sort();
+
+        Self::with_sequence(
+            numbers.into_iter().map(Tile::black).collect(), 
+            sequence.into_iter().map(Tile::black).collect(), 
+        )
+    }
+
+    pub fn with_sequence(tiles: Vec<Tile>, sequence: Vec<Tile>) -> Self {
+        Self {
+            tiles: tiles,
+            sequence: sequence,
+            seed: 0,
+            correct_tiles: Vec::new(),
+            incorrect_tile: None,
+            score: 0,
+            fails: 0,
+        }
+    }
+
+    pub fn generate(size: BoardSize, seed: u64, rule: SequenceRule) -> Self {
+        let sequence: Vec<Tile> = rule.generate(size.count());
+        let mut tiles: Vec<Tile> = sequence.clone();
+        SeededRng::new(seed).shuffle(&mut tiles);
+
+        let mut obj: Self = Self::with_sequence(tiles, sequence);
+        obj.seed = seed;
+
+        obj
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    pub fn get_tile(&self, index: usize) -> Tile {
+        self.tiles[index]
+    }
+
+    pub fn get_number(&self, index: usize) -> i32 {
+        self.tiles[index].number
+    }
+
+    pub fn get_tiles(&self) -> &Vec<Tile> {
+        &self.tiles
+    }
+
+    pub fn get_sequence(&self) -> &Vec<Tile> {
+        &self.sequence
+    }
+
+    pub fn get_score(&self) -> i32 {
+        self.score
+    }
+
+    pub fn get_fails(&self) -> i32 {
+        self.fails
+    }
+
+    pub fn get_progress(&self) -> usize {
+        self.correct_tiles.len()
+    }
+
+    pub fn get_incorrect_tile(&self) -> Option<usize> {
+        self.incorrect_tile
+    }
+
+    pub fn is_cleared(&self, index: usize) -> bool {
+        self.correct_tiles.contains(&index)
+    }
+
+    pub fn is_solved(&self) -> bool {
+        !self.is_empty() && self.correct_tiles.len() == self.tiles.len()
+    }
+
+    pub fn next_expected(&self) -> Option<Tile> {
+        self.sequence.get(self.correct_tiles.len()).copied()
+    }
+
+    // Indexes of the remaining tiles in the order they have to be clicked
+    pub fn get_solution(&self) -> Vec<usize> {
+        let mut solution: Vec<usize> = Vec::new();
+        for tile in self.sequence.iter().skip(self.correct_tiles.len()) {
+            let index: Option<usize> = (0..self.tiles.len())
+                .find(|x| self.tiles[*x] == *tile && !self.is_cleared(*x) && !solution.contains(x));
+            if let Some(index) = index {
+                solution.push(index);
+            }
+        }
+        solution
+    }
+
+    // Remaining tile showing the typed number, the search starts after the given tile,
+    // so tiles with the same number in red and black tables can be switched between
+    pub fn find_number(&self, typed: &str, after: Option<usize>) -> Option<usize> {
+        let start: usize = after.map(|x| x + 1).unwrap_or(0);
+        (0..self.tiles.len())
+            .map(|x| (start + x) % self.tiles.len())
+            .find(|x| !self.is_cleared(*x) && self.tiles[*x].number.to_string() == typed)
+    }
+
+    pub fn has_number_prefix(&self, typed: &str) -> bool {
+        (0..self.tiles.len()).any(|x| !self.is_cleared(x) && self.tiles[x].number.to_string().starts_with(typed))
+    }
+
+    pub fn click(&mut self, index: usize) -> ClickResult {
+        if self.is_cleared(index) {
+            return ClickResult::AlreadyCleared;
+        }
+
+        if Some(self.tiles[index]) == self.next_expected() {
+            self.correct_tiles.push(index);
+            self.incorrect_tile = None;
+            self.score += 1;
+            ClickResult::Correct
+        } else if self.incorrect_tile == Some(index) {
+            // Clicking the same wrong tile again is not counted as a new fail
+            ClickResult::RepeatedWrong
+        } else {
+            self.incorrect_tile = Some(index);
+            self.fails += 1;
+            ClickResult::Wrong
+        }
+    }
+
+    pub fn get_outcome(&self, is_time_over: bool) -> Option<Outcome> {
+        if self.is_solved() {
+            Some(Outcome::Win)
+        } else if is_time_over {
+            Some(Outcome::Lose)
+        } else {
+            None
+        }
+    }
+
+    pub fn reset(&mut self) {
+        // Keeps the same layout, so "Try again" replays the same board
+        self.correct_tiles.clear();
+        self.incorrect_tile = None;
+        self.score = 0;
+        self.fails = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index_of(board: &Board, tile: Tile) -> usize {
+        board.get_tiles().iter().position(|&x| x == tile).unwrap()
+    }
+
+    fn numbers_of(tiles: &[Tile]) -> Vec<i32> {
+        tiles.iter().map(|x| x.number).collect()
+    }
+
+    #[test]
+    fn generated_board_contains_every_number_once() {
+        let board: Board = Board::generate(BoardSize::new(8, 7), 1, SequenceRule::Ascending);
+        let mut numbers: Vec<i32> = numbers_of(board.get_tiles());
+        numbers.sort();
+
+        assert_eq!(numbers, (1..=56).collect::<Vec<i32>>());
+    }
+
+    #[test]
+    fn next_expected_follows_progress() {
+        let mut board: Board = Board::new(vec![3, 1, 2]);
+
+        assert_eq!(board.next_expected(), Some(Tile::black(1)));
+        assert_eq!(board.click(1), ClickResult::Correct);
+        assert_eq!(board.next_expected(), Some(Tile::black(2)));
+        assert_eq!(board.click(2), ClickResult::Correct);
+        assert_eq!(board.click(0), ClickResult::Correct);
+        assert_eq!(board.next_expected(), None);
+    }
+
+    #[test]
+    fn solution_lists_remaining_tiles_in_order() {
+        let mut board: Board = Board::new(vec![3, 1, 2, 1]);
+
+        assert_eq!(board.get_solution(), vec![1, 3, 2, 0]);
+        board.click(1);
+        assert_eq!(board.get_solution(), vec![3, 2, 0]);
+
+        for index in board.get_solution() {
+            assert_eq!(board.click(index), ClickResult::Correct);
+        }
+        assert!(board.is_solved());
+        assert!(board.get_solution().is_empty());
+    }
+
+    #[test]
+    fn typed_number_finds_remaining_tiles() {
+        let mut board: Board = Board::with_sequence(
+            vec![Tile::red(1), Tile::black(12), Tile::black(1), Tile::black(2)],
+            vec![Tile::black(1), Tile::red(1), Tile::black(2), Tile::black(12)],
+        );
+
+        assert_eq!(board.find_number("1", None), Some(0));
+        assert_eq!(board.find_number("1", Some(0)), Some(2));
+        assert_eq!(board.find_number("1", Some(2)), Some(0));
+        assert_eq!(board.find_number("3", None), None);
+        assert!(board.has_number_prefix("1"));
+        assert!(!board.has_number_prefix("3"));
+
+        board.click(2);
+        board.click(0);
+        assert_eq!(board.find_number("1", None), None);
+        assert!(board.has_number_prefix("1"));
+    }
+
+    #[test]
+    fn cursor_stops_at_board_edges() {
+        let size: BoardSize = BoardSize::new(5, 6);
+
+        assert_eq!(size.move_index(0, 1, 0), 1);
+        assert_eq!(size.move_index(0, -1, -1), 0);
+        assert_eq!(size.move_index(4, 1, 1), 9);
+        assert_eq!(size.move_index(27, 0, 1), 27);
+        assert_eq!(size.move_index(27, 0, -2), 17);
+    }
+
+    #[test]
+    fn wrong_click_counts_fail_once_per_tile() {
+        let mut board: Board = Board::new(vec![3, 1, 2]);
+
+        assert_eq!(board.click(0), ClickResult::Wrong);
+        assert_eq!(board.click(0), ClickResult::RepeatedWrong);
+        assert_eq!(board.get_fails(), 1);
+        assert_eq!(board.get_incorrect_tile(), Some(0));
+
+        assert_eq!(board.click(2), ClickResult::Wrong);
+        assert_eq!(board.click(0), ClickResult::Wrong);
+        assert_eq!(board.get_fails(), 3);
+    }
+
+    #[test]
+    fn correct_click_clears_incorrect_tile() {
+        let mut board: Board = Board::new(vec![2, 1]);
+
+        board.click(0);
+        assert_eq!(board.click(1), ClickResult::Correct);
+        assert_eq!(board.get_incorrect_tile(), None);
+        assert_eq!(board.get_score(), 1);
+    }
+
+    #[test]
+    fn cleared_tile_is_ignored() {
+        let mut board: Board = Board::new(vec![1, 2]);
+
+        board.click(0);
+        assert_eq!(board.click(0), ClickResult::AlreadyCleared);
+        assert_eq!(board.get_score(), 1);
+        assert_eq!(board.get_fails(), 0);
+    }
+
+    #[test]
+    fn outcome_detection() {
+        let mut board: Board = Board::generate(BoardSize::new(5, 5), 2, SequenceRule::Ascending);
+
+        assert_eq!(board.get_outcome(false), None);
+        assert_eq!(board.get_outcome(true), Some(Outcome::Lose));
+
+        for number in 1..=25 {
+            let index: usize = index_of(&board, Tile::black(number));
+            board.click(index);
+        }
+        assert!(board.is_solved());
+        assert_eq!(board.get_outcome(false), Some(Outcome::Win));
+        assert_eq!(board.get_outcome(true), Some(Outcome::Win));
+    }
+
+    #[test]
+    fn empty_board_is_not_solved() {
+        let board: Board = Board::default();
+
+        assert!(!board.is_solved());
+        assert_eq!(board.get_outcome(false), None);
+    }
+
+    #[test]
+    fn same_seed_gives_same_layout() {
+        let size: BoardSize = BoardSize::new(8, 7);
+
+        assert_eq!(Board::generate(size, 777, SequenceRule::Ascending).get_tiles(), Board::generate(size, 777, SequenceRule::Ascending).get_tiles());
+        assert_ne!(Board::generate(size, 777, SequenceRule::Ascending).get_tiles(), Board::generate(size, 778, SequenceRule::Ascending).get_tiles());
+    }
+
+    #[test]
+    fn seeded_layout_is_stable() {
+        // Guards against accidental changes of the shuffle, which would break shared seeds
+        let board: Board = Board::generate(BoardSize::new(5, 5), 20251018, SequenceRule::Ascending);
+
+        assert_eq!(board.get_seed(), 20251018);
+        assert_eq!(numbers_of(board.get_tiles()), vec![19, 24, 6, 22, 17, 2, 20, 21, 25, 1, 10, 11, 16, 18, 4, 13, 3, 14, 9, 23, 15, 12, 7, 5, 8]);
+    }
+
+    #[test]
+    fn descending_board_expects_largest_first() {
+        let mut board: Board = Board::generate(BoardSize::new(5, 5), 3, SequenceRule::Descending);
+
+        assert_eq!(board.next_expected(), Some(Tile::black(25)));
+        assert_eq!(board.click(index_of(&board, Tile::black(1))), ClickResult::Wrong);
+        assert_eq!(board.click(index_of(&board, Tile::black(25))), ClickResult::Correct);
+        assert_eq!(board.next_expected(), Some(Tile::black(24)));
+    }
+
+    #[test]
+    fn step_and_offset_boards_are_solvable() {
+        for rule in [SequenceRule::Step(3), SequenceRule::Offset(40)] {
+            let mut board: Board = Board::generate(BoardSize::new(6, 5), 4, rule);
+
+            for tile in rule.generate(30) {
+                assert_eq!(board.next_expected(), Some(tile));
+                assert_eq!(board.click(index_of(&board, tile)), ClickResult::Correct);
+            }
+            assert!(board.is_solved());
+            assert_eq!(board.next_expected(), None);
+        }
+    }
+
+    #[test]
+    fn alternating_board_tells_colors_apart() {
+        let mut board: Board = Board::generate(BoardSize::new(7, 7), 5, SequenceRule::Alternating);
+
+        // Same number in the other color is a wrong click
+        assert_eq!(board.next_expected(), Some(Tile::black(1)));
+        assert_eq!(board.click(index_of(&board, Tile::red(1))), ClickResult::Wrong);
+        assert_eq!(board.click(index_of(&board, Tile::black(1))), ClickResult::Correct);
+        assert_eq!(board.next_expected(), Some(Tile::red(24)));
+        assert_eq!(board.click(index_of(&board, Tile::black(24))), ClickResult::Wrong);
+        assert_eq!(board.click(index_of(&board, Tile::red(24))), ClickResult::Correct);
+        assert_eq!(board.next_expected(), Some(Tile::black(2)));
+    }
+
+    #[test]
+    fn board_size_parsing() {
+        assert_eq!("8x7".parse::<BoardSize>(), Ok(BoardSize::new(8, 7)));
+        assert_eq!(" 10 x 10 ".parse::<BoardSize>(), Ok(BoardSize::new(10, 10)));
+        assert!("4x4".parse::<BoardSize>().is_err());
+        assert!("11x5".parse::<BoardSize>().is_err());
+        assert!("8-7".parse::<BoardSize>().is_err());
+        assert_eq!(BoardSize::new(9, 9).to_string(), "9x9");
+    }
+
+    #[test]
+    fn presets_are_valid() {
+        for size in BoardSize::PRESETS.iter() {
+            assert!(size.is_valid());
+        }
+    }
+
+    #[test]
+    fn reset_keeps_layout() {
+        let mut board: Board = Board::new(vec![2, 1]);
+        board.click(1);
+        board.click(1);
+        board.click(1);
+        board.click(0);
+        board.reset();
+
+        assert_eq!(numbers_of(board.get_tiles()), vec![2, 1]);
+        assert_eq!(board.get_progress(), 0);
+        assert_eq!(board.get_score(), 0);
+        assert_eq!(board.get_fails(), 0);
+        assert_eq!(board.next_expected(), Some(Tile::black(1)));
+    }
+}
@@ -0,0 +1,190 @@
+use std::path::PathBuf;
+
+use crate::game::{ GameDifficulty, GameMode };
+use crate::settings::SettingsOverrides;
+
+pub const USAGE: &str = "Usage: numbers-game [options]
+
+Options:
+    --windowed              Start in a window
+    --fullscreen            Start in fullscreen mode
+    --size <WxH>            Window size, e.g. 1280x720, starts in a window
+    --debug                 Debug mode with short timers and developer tools
+    --difficulty <level>    easy, medium or hard
+    --locale <code>         Language code from locales/codes.xml, e.g. en
+    --seed <number>         Seed of the board to play
+    --config <path>         Settings file to use instead of the default one
+    --validate-locales      Check locale files and exit
+    --help                  Show this message and exit
+
+Difficulty, language and window mode given here are used for this session only
+and are not saved to the settings file.";
+
+pub const MIN_WINDOW_WIDTH: i32 = 800;
+pub const MIN_WINDOW_HEIGHT: i32 = 600;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LaunchOptions {
+    pub mode: GameMode,
+    pub window_size: Option<(i32, i32)>,
+    pub seed: Option<u64>,
+    pub config_path: Option<PathBuf>,
+    pub overrides: SettingsOverrides,
+}
+
+impl Default for LaunchOptions {
+    fn default() -> Self {
+        Self {
+            mode: GameMode::Release,
+            window_size: None,
+            seed: None,
+            config_path: None,
+            overrides: SettingsOverrides::default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Command {
+    Play(LaunchOptions),
+    ValidateLocales,
+    Help,
+}
+
+// Arguments without the name of the executable
+pub fn parse(args: &[String]) -> Result<Command, String> {
+    let mut options: LaunchOptions = LaunchOptions::default();
+    let mut index: usize = 0;
+
+    while index < args.len() {
+        let arg: &str = args[index].as_str();
+        let mut get_value = || -> Result<&str, String> {
+            index += 1;
+            args.get(index).map(|x| x.as_str()).ok_or_else(|| format!("Option {} requires a value", arg))
+        };
+
+        match arg {
+            "--help" | "-h" => return Ok(Command::Help),
+            "--validate-locales" => return Ok(Command::ValidateLocales),
+            "--windowed" => set_fullscreen(&mut options, false)?,
+            "--fullscreen" => set_fullscreen(&mut options, true)?,
+            "--debug" => options.mode = GameMode::Debug,
+            "--size" => options.window_size = Some(parse_window_size(get_value()?)?),
+            "--difficulty" => options.overrides.difficulty = Some(parse_difficulty(get_value()?)?),
+            "--locale" => options.overrides.locale = Some(get_value()?.to_string()),
+            "--seed" => {
+                let value: &str = get_value()?;
+                options.seed = Some(value.parse::<u64>().map_err(|_| format!("Invalid seed \"{}\", expected a positive number", value))?);
+            },
+            "--config" => options.config_path = Some(PathBuf::from(get_value()?)),
+            x => return Err(format!("Unknown option \"{}\"", x)),
+        }
+        index += 1;
+    }
+
+    // Fullscreen mode would take the size of the monitor, so the given size needs a window
+    if options.window_size.is_some() {
+        if options.overrides.is_fullscreen == Some(true) {
+            return Err("Options --size and --fullscreen can't be used together".to_string());
+        }
+        options.overrides.is_fullscreen = Some(false);
+    }
+
+    Ok(Command::Play(options))
+}
+
+// Locales are known only after their files are read, so the language is checked apart from the other options
+pub fn check_locale(options: &LaunchOptions, locale_codes: &[String]) -> Result<(), String> {
+    match &options.overrides.locale {
+        Some(code) if !locale_codes.contains(code) => Err(format!("Unknown locale \"{}\", available: {}", code, locale_codes.join(", "))),
+        _ => Ok(()),
+    }
+}
+
+fn set_fullscreen(options: &mut LaunchOptions, is_fullscreen: bool) -> Result<(), String> {
+    if options.overrides.is_fullscreen == Some(!is_fullscreen) {
+        return Err("Options --windowed and --fullscreen can't be used together".to_string());
+    }
+    options.overrides.is_fullscreen = Some(is_fullscreen);
+    Ok(())
+}
+
+fn parse_window_size(value: &str) -> Result<(i32, i32), String> {
+    let error: String = format!("Invalid window size \"{}\", expected WIDTHxHEIGHT of at least {}x{}", value, MIN_WINDOW_WIDTH, MIN_WINDOW_HEIGHT);
+    let (width, height) = value.split_once('x').ok_or_else(|| error.clone())?;
+    let width: i32 = width.parse::<i32>().map_err(|_| error.clone())?;
+    let height: i32 = height.parse::<i32>().map_err(|_| error.clone())?;
+
+    if width < MIN_WINDOW_WIDTH || height < MIN_WINDOW_HEIGHT {
+        return Err(error);
+    }
+    Ok((width, height))
+}
+
+fn parse_difficulty(value: &str) -> Result<GameDifficulty, String> {
+    match value.to_lowercase().as_str() {
+        "easy" => Ok(GameDifficulty::Easy),
+        "medium" => Ok(GameDifficulty::Medium),
+        "hard" => Ok(GameDifficulty::Hard),
+        _ => Err(format!("Invalid difficulty \"{}\", expected easy, medium or hard", value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_args(args: &str) -> Result<Command, String> {
+        parse(&args.split_whitespace().map(|x| x.to_string()).collect::<Vec<String>>())
+    }
+
+    #[test]
+    fn parses_launch_options() {
+        let command: Command = parse_args("--windowed --size 1280x720 --debug --difficulty Hard --locale ua --seed 42 --config /tmp/game.cfg").unwrap();
+
+        assert_eq!(command, Command::Play(LaunchOptions {
+            mode: GameMode::Debug,
+            window_size: Some((1280, 720)),
+            seed: Some(42),
+            config_path: Some(PathBuf::from("/tmp/game.cfg")),
+            overrides: SettingsOverrides {
+                locale: Some("ua".to_string()),
+                difficulty: Some(GameDifficulty::Hard),
+                is_fullscreen: Some(false),
+            },
+        }));
+        assert_eq!(parse_args(""), Ok(Command::Play(LaunchOptions::default())));
+        assert_eq!(parse_args("--size 1024x768"), Ok(Command::Play(LaunchOptions {
+            window_size: Some((1024, 768)),
+            overrides: SettingsOverrides {
+                is_fullscreen: Some(false),
+                ..SettingsOverrides::default()
+            },
+            ..LaunchOptions::default()
+        })));
+        assert_eq!(parse_args("--seed 1 --help"), Ok(Command::Help));
+    }
+
+    #[test]
+    fn reports_invalid_options() {
+        assert_eq!(parse_args("--size"), Err("Option --size requires a value".to_string()));
+        assert!(parse_args("--size 100x100").is_err());
+        assert!(parse_args("--size big").is_err());
+        assert!(parse_args("--difficulty insane").is_err());
+        assert!(parse_args("--seed -5").is_err());
+        assert!(parse_args("--windowed --fullscreen").is_err());
+        assert_eq!(parse_args("--fullscreen --size 1280x720"), Err("Options --size and --fullscreen can't be used together".to_string()));
+        assert_eq!(parse_args("--colour blue"), Err("Unknown option \"--colour\"".to_string()));
+
+        let codes: Vec<String> = vec!["en".to_string(), "de".to_string()];
+        let options: LaunchOptions = LaunchOptions {
+            overrides: SettingsOverrides {
+                locale: Some("fr".to_string()),
+                ..SettingsOverrides::default()
+            },
+            ..LaunchOptions::default()
+        };
+        assert_eq!(check_locale(&options, &codes), Err("Unknown locale \"fr\", available: en, de".to_string()));
+        assert_eq!(check_locale(&LaunchOptions::default(), &codes), Ok(()));
+    }
+}
@@ -0,0 +1,58 @@
+pub const HELP: &str = "Commands: win, lose, time <secs>, solve <tiles>";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DebugCommand {
+    Win,
+    Lose,
+    Time(f64),
+    Solve(usize),
+}
+
+impl std::str::FromStr for DebugCommand {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut words = value.split_whitespace();
+        let name: String = words.next().unwrap_or("").to_lowercase();
+        let arg: Option<&str> = words.next();
+        if words.next().is_some() {
+            return Err(format!("Too many arguments: {}", value.trim()));
+        }
+
+        match (name.as_str(), arg) {
+            ("win", None) => Ok(Self::Win),
+            ("lose", None) => Ok(Self::Lose),
+            ("time", Some(x)) => match x.parse::<f64>() {
+                Ok(secs) if secs >= 0.0 && secs.is_finite() => Ok(Self::Time(secs)),
+                _ => Err(format!("Invalid time: {}", x)),
+            },
+            ("solve", Some(x)) => x.parse::<usize>().map(Self::Solve).map_err(|_| format!("Invalid number of tiles: {}", x)),
+            ("time", None) | ("solve", None) => Err(format!("Command \"{}\" requires a number", name)),
+            ("", _) => Err(HELP.to_string()),
+            _ => Err(format!("Unknown command \"{}\". {}", value.trim(), HELP)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_commands() {
+        assert_eq!("win".parse::<DebugCommand>(), Ok(DebugCommand::Win));
+        assert_eq!(" LOSE ".parse::<DebugCommand>(), Ok(DebugCommand::Lose));
+        assert_eq!("time 5.5".parse::<DebugCommand>(), Ok(DebugCommand::Time(5.5)));
+        assert_eq!("solve 10".parse::<DebugCommand>(), Ok(DebugCommand::Solve(10)));
+    }
+
+    #[test]
+    fn rejects_invalid_commands() {
+        assert_eq!("time".parse::<DebugCommand>(), Err("Command \"time\" requires a number".to_string()));
+        assert!("time -3".parse::<DebugCommand>().is_err());
+        assert!("solve all".parse::<DebugCommand>().is_err());
+        assert!("win now".parse::<DebugCommand>().is_err());
+        assert_eq!("".parse::<DebugCommand>(), Err(HELP.to_string()));
+        assert!("teleport".parse::<DebugCommand>().unwrap_err().starts_with("Unknown command \"teleport\""));
+    }
+}
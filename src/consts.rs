@@ -3,33 +3,145 @@ pub const CHOOSE_LANGUAGE_STRING_NAME: &str = "choose_language";
 pub const EASY_DIFFICULTY_STRING_NAME: &str = "easy_difficulty";
 pub const MEDIUM_DIFFICULTY_STRING_NAME: &str = "medium_difficulty";
 pub const HARD_DIFFICULTY_STRING_NAME: &str = "hard_difficulty";
+pub const COUNTDOWN_MODE_STRING_NAME: &str = "countdown_mode";
+pub const TIME_ATTACK_MODE_STRING_NAME: &str = "time_attack_mode";
 pub const TRY_AGAIN_BTN_STRING_NAME: &str = "try_again_btn";
 pub const EXIT_BTN_STRING_NAME: &str = "exit_btn";
-pub const SCORE_RESULT_1_STRING_NAME: &str = "score_result_1";
-pub const SCORE_RESULT_2_STRING_NAME: &str = "score_result_2";
+pub const SCORE_RESULT_STRING_NAME: &str = "score_result";
 pub const WIN_RESULT_STRING_NAME: &str = "win_result";
-pub const LOSE_RESULT_1_STRING_NAME: &str = "lose_result_1";
-pub const LOSE_RESULT_2_STRING_NAME: &str = "lose_result_2";
-pub const LOSE_RESULT_3_STRING_NAME: &str = "lose_result_3";
+pub const SEED_RESULT_STRING_NAME: &str = "seed_result";
+pub const DAILY_RESULT_STRING_NAME: &str = "daily_result";
+pub const DAILY_PRACTICE_STRING_NAME: &str = "daily_practice";
+pub const DAILY_TODAY_STRING_NAME: &str = "daily_today";
+pub const DAILY_UNFINISHED_STRING_NAME: &str = "daily_unfinished";
+pub const NEXT_NUMBER_STRING_NAME: &str = "next_number";
+pub const TYPED_NUMBER_STRING_NAME: &str = "typed_number";
+pub const SCORE_CLICKS_STRING_NAME: &str = "score_clicks";
+pub const SCORE_STREAK_STRING_NAME: &str = "score_streak";
+pub const SCORE_SPEED_STRING_NAME: &str = "score_speed";
+pub const SCORE_FAILS_STRING_NAME: &str = "score_fails";
+pub const SCORE_TOTAL_STRING_NAME: &str = "score_total";
+pub const TIME_RESULT_STRING_NAME: &str = "time_result";
+pub const NEW_RECORD_STRING_NAME: &str = "new_record";
+pub const RECORD_PLACE_STRING_NAME: &str = "record_place";
+pub const LOSE_RESULT_STRING_NAME: &str = "lose_result";
 pub const ENABLE_BTN_STRING_NAME: &str = "enable_btn";
 pub const DISABLE_BTN_STRING_NAME: &str = "disable_btn";
 pub const START_MENU_STRING_NAME: &str = "start_menu";
 pub const CONTINUE_MENU_STRING_NAME: &str = "continue_menu";
+pub const DAILY_MENU_STRING_NAME: &str = "daily_menu";
+pub const RECORDS_MENU_STRING_NAME: &str = "records_menu";
+pub const RECORDS_TITLE_STRING_NAME: &str = "records_title";
+pub const RECORDS_EMPTY_STRING_NAME: &str = "records_empty";
+pub const RECORDS_SCORE_STRING_NAME: &str = "records_score";
+pub const RECORDS_FAILS_STRING_NAME: &str = "records_fails";
+pub const RECORDS_TIME_STRING_NAME: &str = "records_time";
+pub const RECORDS_SEED_STRING_NAME: &str = "records_seed";
+pub const RECORDS_DATE_STRING_NAME: &str = "records_date";
 pub const SETTINGS_MENU_STRING_NAME: &str = "settings_menu";
 pub const HELP_MENU_STRING_NAME: &str = "help_menu";
 pub const EXIT_MENU_STRING_NAME: &str = "exit_menu";
 pub const BACK_MENU_STRING_NAME: &str = "back_menu";
 pub const DIFFICULTY_MENU_STRING_NAME: &str = "difficulty_menu";
+pub const PLAY_MODE_MENU_STRING_NAME: &str = "play_mode_menu";
 pub const LANGUAGE_MENU_STRING_NAME: &str = "language_menu";
 pub const FULLSCREEN_MENU_STRING_NAME: &str = "fullscreen_menu";
 pub const TOGGLE_FPS_MENU_STRING_NAME: &str = "toggle_fps_menu";
+pub const BOARD_SIZE_MENU_STRING_NAME: &str = "board_size_menu";
+pub const AUTO_BOARD_SIZE_STRING_NAME: &str = "auto_board_size";
+pub const SEQUENCE_MENU_STRING_NAME: &str = "sequence_menu";
+pub const SEQUENCE_ASCENDING_STRING_NAME: &str = "sequence_ascending";
+pub const SEQUENCE_DESCENDING_STRING_NAME: &str = "sequence_descending";
+pub const SEQUENCE_STEP_STRING_NAME: &str = "sequence_step";
+pub const SEQUENCE_OFFSET_STRING_NAME: &str = "sequence_offset";
+pub const SEQUENCE_ALTERNATING_STRING_NAME: &str = "sequence_alternating";
 pub const HELP_TITLE_1_STRING_NAME: &str = "help_title_1";
 pub const HELP_TEXT_1_STRING_NAME: &str = "help_text_1";
+pub const HELP_TEXT_SEQUENCE_STRING_NAME: &str = "help_text_sequence";
 pub const HELP_TEXT_2_STRING_NAME: &str = "help_text_2";
+pub const HELP_TEXT_2_TIME_ATTACK_STRING_NAME: &str = "help_text_2_time_attack";
 pub const HELP_TEXT_3_1_STRING_NAME: &str = "help_text_3_1";
 pub const HELP_TEXT_3_2_STRING_NAME: &str = "help_text_3_2";
 pub const HELP_TITLE_2_STRING_NAME: &str = "help_title_2";
 pub const HELP_TEXT_4_STRING_NAME: &str = "help_text_4";
 pub const HELP_TEXT_5_STRING_NAME: &str = "help_text_5";
 pub const HELP_TEXT_6_STRING_NAME: &str = "help_text_6";
-pub const HELP_TITLE_3_STRING_NAME: &str = "help_title_3";
\ No newline at end of file
+pub const HELP_TITLE_3_STRING_NAME: &str = "help_title_3";
+
+// Characters every font atlas gets besides the ones used in translations: digits, latin letters and UI symbols
+pub const FONT_BASE_GLYPHS: &str = " !\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~";
+// Bundled font of the locales which don't declare their own, relative to the assets folder
+pub const DEFAULT_FONT_PATH: &str = "fonts/Arimo-Regular.ttf";
+
+// Every key the game looks up, each locale is expected to translate all of them
+pub const ALL_STRING_NAMES: [&str; 69] = [
+    GAME_TITLE_STRING_NAME,
+    CHOOSE_LANGUAGE_STRING_NAME,
+    EASY_DIFFICULTY_STRING_NAME,
+    MEDIUM_DIFFICULTY_STRING_NAME,
+    HARD_DIFFICULTY_STRING_NAME,
+    COUNTDOWN_MODE_STRING_NAME,
+    TIME_ATTACK_MODE_STRING_NAME,
+    TRY_AGAIN_BTN_STRING_NAME,
+    EXIT_BTN_STRING_NAME,
+    SCORE_RESULT_STRING_NAME,
+    WIN_RESULT_STRING_NAME,
+    SEED_RESULT_STRING_NAME,
+    DAILY_RESULT_STRING_NAME,
+    DAILY_PRACTICE_STRING_NAME,
+    DAILY_TODAY_STRING_NAME,
+    DAILY_UNFINISHED_STRING_NAME,
+    NEXT_NUMBER_STRING_NAME,
+    TYPED_NUMBER_STRING_NAME,
+    SCORE_CLICKS_STRING_NAME,
+    SCORE_STREAK_STRING_NAME,
+    SCORE_SPEED_STRING_NAME,
+    SCORE_FAILS_STRING_NAME,
+    SCORE_TOTAL_STRING_NAME,
+    TIME_RESULT_STRING_NAME,
+    NEW_RECORD_STRING_NAME,
+    RECORD_PLACE_STRING_NAME,
+    LOSE_RESULT_STRING_NAME,
+    ENABLE_BTN_STRING_NAME,
+    DISABLE_BTN_STRING_NAME,
+    START_MENU_STRING_NAME,
+    CONTINUE_MENU_STRING_NAME,
+    DAILY_MENU_STRING_NAME,
+    RECORDS_MENU_STRING_NAME,
+    RECORDS_TITLE_STRING_NAME,
+    RECORDS_EMPTY_STRING_NAME,
+    RECORDS_SCORE_STRING_NAME,
+    RECORDS_FAILS_STRING_NAME,
+    RECORDS_TIME_STRING_NAME,
+    RECORDS_SEED_STRING_NAME,
+    RECORDS_DATE_STRING_NAME,
+    SETTINGS_MENU_STRING_NAME,
+    HELP_MENU_STRING_NAME,
+    EXIT_MENU_STRING_NAME,
+    BACK_MENU_STRING_NAME,
+    DIFFICULTY_MENU_STRING_NAME,
+    PLAY_MODE_MENU_STRING_NAME,
+    LANGUAGE_MENU_STRING_NAME,
+    FULLSCREEN_MENU_STRING_NAME,
+    TOGGLE_FPS_MENU_STRING_NAME,
+    BOARD_SIZE_MENU_STRING_NAME,
+    AUTO_BOARD_SIZE_STRING_NAME,
+    SEQUENCE_MENU_STRING_NAME,
+    SEQUENCE_ASCENDING_STRING_NAME,
+    SEQUENCE_DESCENDING_STRING_NAME,
+    SEQUENCE_STEP_STRING_NAME,
+    SEQUENCE_OFFSET_STRING_NAME,
+    SEQUENCE_ALTERNATING_STRING_NAME,
+    HELP_TITLE_1_STRING_NAME,
+    HELP_TEXT_1_STRING_NAME,
+    HELP_TEXT_SEQUENCE_STRING_NAME,
+    HELP_TEXT_2_STRING_NAME,
+    HELP_TEXT_2_TIME_ATTACK_STRING_NAME,
+    HELP_TEXT_3_1_STRING_NAME,
+    HELP_TEXT_3_2_STRING_NAME,
+    HELP_TITLE_2_STRING_NAME,
+    HELP_TEXT_4_STRING_NAME,
+    HELP_TEXT_5_STRING_NAME,
+    HELP_TEXT_6_STRING_NAME,
+    HELP_TITLE_3_STRING_NAME,
+];
\ No newline at end of file
@@ -0,0 +1,149 @@
+use chrono::{ Datelike, Local, NaiveDate };
+
+use crate::board::BoardSize;
+use crate::game::{ GameDifficulty, PlayMode };
+use crate::records::Record;
+use crate::rng::SeededRng;
+use crate::sequence::SequenceRule;
+
+// Everyone plays the daily challenge with the same rules, so results can be compared
+pub const BOARD_SIZE: BoardSize = BoardSize::new(8, 7);
+pub const DIFFICULTY: GameDifficulty = GameDifficulty::Medium;
+pub const PLAY_MODE: PlayMode = PlayMode::Countdown;
+pub const SEQUENCE_RULE: SequenceRule = SequenceRule::Ascending;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DailyRun {
+    pub date: NaiveDate,
+    pub is_practice: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DailyAttempt {
+    Started,
+    Finished(Record),
+}
+
+// Scored attempts of the daily challenge by date. The attempt is added when its board is shown and gets
+// the result when the run ends, so a run that was quit or crashed still uses up the day
+#[derive(Clone, Debug, Default)]
+pub struct DailyLog {
+    entries: Vec<(NaiveDate, DailyAttempt)>,
+}
+
+impl DailyLog {
+    pub fn parse(data: &str) -> Self {
+        // Broken lines are skipped, like in the records file
+        Self {
+            entries: data.lines().filter_map(Self::parse_line).collect(),
+        }
+    }
+
+    fn parse_line(line: &str) -> Option<(NaiveDate, DailyAttempt)> {
+        let values: Vec<&str> = line.split(',').map(|x| x.trim()).collect();
+        let date: NaiveDate = values.first()?.parse::<NaiveDate>().ok()?;
+        match values.len() {
+            2 if values[1] == "started" => Some((date, DailyAttempt::Started)),
+            5 => Some((date, DailyAttempt::Finished(Record {
+                score: values[1].parse::<i32>().ok()?,
+                fails: values[2].parse::<i32>().ok()?,
+                time: values[3].parse::<f64>().ok()?,
+                seed: values[4].parse::<u64>().ok()?,
+                date: date,
+            }))),
+            _ => None,
+        }
+    }
+
+    pub fn serialize(&self) -> String {
+        self.entries.iter()
+            .map(|(date, attempt)| match attempt {
+                DailyAttempt::Started => format!("{},started", date),
+                DailyAttempt::Finished(record) => format!("{},{},{},{:.3},{}", date, record.score, record.fails, record.time, record.seed),
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    pub fn get(&self, date: NaiveDate) -> Option<DailyAttempt> {
+        self.entries.iter().find(|(x, _)| *x == date).map(|(_, attempt)| *attempt)
+    }
+
+    // Returns false when the attempt of the day is already used
+    pub fn start(&mut self, date: NaiveDate) -> bool {
+        if self.get(date).is_some() {
+            return false;
+        }
+        self.entries.push((date, DailyAttempt::Started));
+        true
+    }
+
+    pub fn finish(&mut self, date: NaiveDate, record: Record) {
+        // Result is kept only for the attempt started that day, practice runs can't replace it
+        if let Some((_, attempt)) = self.entries.iter_mut().find(|(x, _)| *x == date) {
+            if *attempt == DailyAttempt::Started {
+                *attempt = DailyAttempt::Finished(Record { date: date, ..record });
+            }
+        }
+    }
+}
+
+pub fn get_today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+pub fn get_seed(date: NaiveDate) -> u64 {
+    // Date is mixed through the generator, so neighbouring days get unrelated boards
+    let value: u64 = date.year() as u64 * 10000 + date.month() as u64 * 100 + date.day() as u64;
+    SeededRng::new(value).next_u64() % 1_000_000_000
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seed_depends_only_on_date() {
+        let date: NaiveDate = NaiveDate::from_ymd_opt(2025, 3, 14).unwrap();
+
+        assert_eq!(get_seed(date), get_seed(NaiveDate::from_ymd_opt(2025, 3, 14).unwrap()));
+        assert_ne!(get_seed(date), get_seed(date.succ_opt().unwrap()));
+        assert!(get_seed(date) < 1_000_000_000);
+    }
+
+    #[test]
+    fn keeps_one_attempt_per_day() {
+        let date: NaiveDate = NaiveDate::from_ymd_opt(2025, 3, 14).unwrap();
+        let record: Record = Record {
+            score: 420,
+            fails: 2,
+            time: 75.25,
+            seed: get_seed(date),
+            date: date.succ_opt().unwrap(),
+        };
+        let mut log: DailyLog = DailyLog::default();
+
+        assert!(log.start(date));
+        assert!(!log.start(date));
+        assert_eq!(log.get(date), Some(DailyAttempt::Started));
+        assert_eq!(log.get(date.succ_opt().unwrap()), None);
+
+        log.finish(date, record);
+        log.finish(date, Record { score: 999, ..record });
+        assert_eq!(log.get(date), Some(DailyAttempt::Finished(Record { date: date, ..record })));
+    }
+
+    #[test]
+    fn survives_serialization() {
+        let date: NaiveDate = NaiveDate::from_ymd_opt(2025, 3, 14).unwrap();
+        let mut log: DailyLog = DailyLog::default();
+        log.start(date);
+        log.finish(date, Record { score: 420, fails: 2, time: 75.25, seed: 7, date: date });
+        log.start(date.succ_opt().unwrap());
+
+        let parsed: DailyLog = DailyLog::parse(&format!("{}\nbroken line", log.serialize()));
+
+        assert_eq!(parsed.get(date), log.get(date));
+        assert_eq!(parsed.get(date.succ_opt().unwrap()), Some(DailyAttempt::Started));
+    }
+}
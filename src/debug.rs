@@ -0,0 +1,160 @@
+use raylib::prelude::*;
+use raylib::consts::KeyboardKey::*;
+
+use crate::board::Tile;
+use crate::console::{ self, DebugCommand };
+use crate::game::{ self, GameMode, GameState };
+use crate::level::Level;
+use crate::menu::Menu;
+use crate::timer::{ Timer, TimerMode };
+
+const FONT_SIZE: i32 = 20;
+const LINE_HEIGHT: i32 = 24;
+const SOLUTION_PREVIEW_LEN: usize = 10;
+
+// Developer tools of the debug mode: F3 shows the state of the game, "`" opens the console
+#[derive(Default)]
+pub struct DebugOverlay {
+    is_visible: bool,
+    is_console_open: bool,
+    input: String,
+    message: String,
+}
+
+impl DebugOverlay {
+    // Returns true when the console takes the keyboard, so the game doesn't react to typing
+    pub fn process_controller(&mut self, rl: &mut RaylibHandle, game: &mut game::Game, level: &mut Level) -> bool {
+        if game.get_mode() != GameMode::Debug {
+            return false;
+        }
+
+        if !self.is_console_open {
+            if rl.is_key_released(KEY_F3) {
+                self.is_visible = !self.is_visible;
+            }
+            if rl.is_key_released(KEY_GRAVE) {
+                self.is_console_open = true;
+                self.is_visible = true;
+                self.message = console::HELP.to_string();
+                // Character of the key which opened the console is not typed in
+                while rl.get_char_pressed().is_some() {}
+            }
+            return false;
+        }
+
+        while let Some(c) = rl.get_char_pressed() {
+            if c != '`' {
+                self.input.push(c);
+            }
+        }
+        if rl.is_key_pressed(KEY_BACKSPACE) || rl.is_key_pressed_repeat(KEY_BACKSPACE) {
+            self.input.pop();
+        }
+        if rl.is_key_released(KEY_ENTER) {
+            match self.input.parse::<DebugCommand>() {
+                Ok(command) => {
+                    // Console is closed, so the level gets to the result screen right away
+                    self.message = Self::execute(command, game, level);
+                    self.is_console_open = false;
+                },
+                Err(e) => self.message = e,
+            }
+            self.input.clear();
+        }
+        if rl.is_key_released(KEY_ESCAPE) || rl.is_key_released(KEY_GRAVE) {
+            self.is_console_open = false;
+            self.input.clear();
+        }
+
+        true
+    }
+
+    fn execute(command: DebugCommand, game: &game::Game, level: &mut Level) -> String {
+        if game.get_state() != GameState::Game {
+            return "Commands work only during a game".to_string();
+        }
+
+        match command {
+            DebugCommand::Win => {
+                let count: usize = level.solve(usize::MAX);
+                format!("Cleared {} tiles", count)
+            },
+            DebugCommand::Lose => {
+                if level.get_timer().get_mode() == TimerMode::Stopwatch {
+                    return "Time attack can't be lost, use \"win\" instead".to_string();
+                }
+                level.set_time(0.0);
+                "Time is over".to_string()
+            },
+            DebugCommand::Time(secs) => {
+                level.set_time(secs);
+                format!("Timer is set to {}s", secs)
+            },
+            DebugCommand::Solve(count) => {
+                // The last tile is left for the player, otherwise "solve" would be the same as "win"
+                let remaining: usize = level.get_board().get_solution().len();
+                let count: usize = level.solve(count.min(remaining.saturating_sub(1)));
+                format!("Cleared {} tiles", count)
+            },
+        }
+    }
+
+    pub fn draw(&self, d: &mut RaylibDrawHandle, game: &game::Game, menu: &Menu, level: &Level) {
+        if game.get_mode() != GameMode::Debug || !self.is_visible {
+            return;
+        }
+
+        let mut lines: Vec<String> = vec![
+            format!("Game: {:?}, menu: {:?}", game.get_state(), menu.get_state()),
+        ];
+
+        if level.is_started() {
+            let next: String = match level.get_board().next_expected() {
+                Some(tile) => Self::format_tile(tile),
+                None => "-".to_string(),
+            };
+            let solution: Vec<usize> = level.get_board().get_solution();
+            let solution_str: Vec<String> = solution.iter().take(SOLUTION_PREVIEW_LEN).map(|x| x.to_string()).collect();
+            lines.push(format!("Next: {}, solution: {}{}", next, solution_str.join(", "), if solution.len() > SOLUTION_PREVIEW_LEN { ", ..." } else { "" }));
+            lines.push(Self::format_timer(level.get_timer()));
+
+            // Tiles of the solution are numbered in the order they have to be clicked
+            for (order, index) in solution.iter().take(SOLUTION_PREVIEW_LEN).enumerate() {
+                let rec: &Rectangle = &level.get_tile_recs()[*index];
+                let color: Color = if order == 0 { Color::BLUE } else { Color::SKYBLUE };
+                d.draw_rectangle_lines_ex(rec, 3.0, color);
+                d.draw_text(&(order + 1).to_string(), rec.x as i32 + 4, rec.y as i32 + 2, FONT_SIZE, color);
+            }
+        }
+
+        if self.is_console_open {
+            lines.push(self.message.clone());
+            lines.push(format!("> {}_", self.input));
+        } else if !self.message.is_empty() {
+            lines.push(self.message.clone());
+        }
+
+        // Panel is placed under the FPS counter
+        let width: i32 = lines.iter().map(|x| d.measure_text(x, FONT_SIZE)).max().unwrap_or(0) + 20;
+        let height: i32 = lines.len() as i32 * LINE_HEIGHT + 10;
+        d.draw_rectangle(0, 40, width, height, Color::BLACK.alpha(0.7));
+        for (i, line) in lines.iter().enumerate() {
+            d.draw_text(line, 10, 45 + i as i32 * LINE_HEIGHT, FONT_SIZE, Color::WHITE);
+        }
+    }
+
+    fn format_tile(tile: Tile) -> String {
+        format!("{} ({:?})", tile.number, tile.color)
+    }
+
+    fn format_timer(timer: &Timer) -> String {
+        format!(
+            "Timer: running = {}, start = {:.3}, pause = {:.3}, duration = {:.1}, elapsed = {:.3}",
+            timer.is_active(),
+            timer.get_start_time(),
+            timer.get_pause_time(),
+            timer.get_duration(),
+            timer.get_elapsed_time(),
+        )
+    }
+}
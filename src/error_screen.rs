@@ -0,0 +1,63 @@
+use raylib::prelude::*;
+
+use crate::assets::AssetError;
+use crate::paths;
+
+const TITLE_FONT_SIZE: i32 = 40;
+const TEXT_FONT_SIZE: i32 = 24;
+const PADDING: i32 = 60;
+
+// Shown instead of the game when required assets can't be loaded. Locales and the custom font
+// may be the broken assets themselves, so the text is in English and drawn with the default font
+pub fn show(rl: &mut RaylibHandle, thread: &RaylibThread, error: &AssetError) {
+    eprintln!("{}", error);
+
+    let rows: [String; 4] = [
+        error.to_string(),
+        format!("Assets folder: {}", paths::get_assets_dir().display()),
+        format!("The folder can be set with {} environment variable.", paths::ASSETS_DIR_ENV),
+        "Reinstalling the game should restore missing files.".to_string(),
+    ];
+
+    rl.set_window_title(thread, "Numbers game - error");
+    while !rl.window_should_close() {
+        let max_width: i32 = rl.get_screen_width() - PADDING * 2;
+        let lines: Vec<Vec<String>> = rows.iter().map(|x| wrap_text(rl, x, max_width)).collect();
+
+        let mut d = rl.begin_drawing(thread);
+        d.clear_background(Color::WHITE);
+
+        let mut y: i32 = PADDING;
+        d.draw_text("Unable to start the game", PADDING, y, TITLE_FONT_SIZE, Color::RED);
+        y += TITLE_FONT_SIZE * 2;
+
+        for row in lines.iter() {
+            for line in row.iter() {
+                d.draw_text(line, PADDING, y, TEXT_FONT_SIZE, Color::BLACK);
+                y += TEXT_FONT_SIZE + TEXT_FONT_SIZE / 2;
+            }
+            y += TEXT_FONT_SIZE;
+        }
+
+        d.draw_text("Press Esc to close the window", PADDING, d.get_screen_height() - PADDING - TEXT_FONT_SIZE, TEXT_FONT_SIZE, Color::GRAY);
+    }
+}
+
+fn wrap_text(rl: &RaylibHandle, text: &str, max_width: i32) -> Vec<String> {
+    // Long paths and error messages don't fit into the window in one line
+    let mut lines: Vec<String> = Vec::new();
+    let mut line: String = String::new();
+
+    for word in text.split(' ') {
+        let candidate: String = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+        if !line.is_empty() && rl.measure_text(&candidate, TEXT_FONT_SIZE) > max_width {
+            lines.push(line);
+            line = word.to_string();
+        } else {
+            line = candidate;
+        }
+    }
+    lines.push(line);
+
+    lines
+}
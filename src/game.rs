@@ -2,21 +2,33 @@
 
 use raylib::prelude::*;
 use raylib::consts::KeyboardKey::*;
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
+use chrono::NaiveDate;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::SystemTime;
 
+use crate::board::BoardSize;
+use crate::assets::{ self, AssetError };
 use crate::consts;
+use crate::daily::{ self, DailyAttempt, DailyLog };
+use crate::input::{ Action, Input };
 use crate::menu::{ Menu, MenuState };
+use crate::paths;
+use crate::records::{ Record, RecordKey, RecordTable };
+use crate::sequence::SequenceRule;
+use crate::settings::{ LoadedSettings, Settings, SettingsOverrides, SETTINGS_VERSION };
+use crate::timer::TimeRules;
 use crate::level::Level;
 use crate::localization::Locale;
+use crate::translations;
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum GameMode {
     Debug,
     Release,
 }
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum GameState {
     Menu,
     Game,
@@ -37,6 +49,19 @@ impl std::fmt::Display for GameDifficulty {
     }
 }
 
+impl std::str::FromStr for GameDifficulty {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim() {
+            "Easy" => Ok(Self::Easy),
+            "Medium" => Ok(Self::Medium),
+            "Hard" => Ok(Self::Hard),
+            x => Err(format!("Unknown difficulty: {}", x)),
+        }
+    }
+}
+
 impl GameDifficulty {
     pub fn repr(&self) -> &str {
         match *self {
@@ -45,28 +70,84 @@ impl GameDifficulty {
             Self::Hard => consts::HARD_DIFFICULTY_STRING_NAME,
         }
     }
+
+    pub fn get_board_size(&self) -> BoardSize {
+        match *self {
+            Self::Easy => BoardSize::new(6, 6),
+            Self::Medium => BoardSize::new(8, 7),
+            Self::Hard => BoardSize::new(9, 8),
+        }
+    }
+
+    pub fn get_time_rules(&self) -> TimeRules {
+        match *self {
+            Self::Easy => TimeRules { correct_click_bonus: 1.0, wrong_click_penalty: 0.0 },
+            Self::Medium => TimeRules { correct_click_bonus: 1.0, wrong_click_penalty: 3.0 },
+            Self::Hard => TimeRules { correct_click_bonus: 0.0, wrong_click_penalty: 3.0 },
+        }
+    }
 }
 
-#[derive(Clone, Copy, PartialEq)]
-pub struct GameSettings {
-    pub is_fullscreen: bool,
-    pub is_vsync: bool,
-    pub is_fps_visible: bool,
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PlayMode {
+    Countdown,
+    TimeAttack,
+}
+
+impl std::fmt::Display for PlayMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            Self::Countdown => write!(f, "countdown"),
+            Self::TimeAttack => write!(f, "time_attack"),
+        }
+    }
+}
+
+impl std::str::FromStr for PlayMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim() {
+            "countdown" => Ok(Self::Countdown),
+            "time_attack" => Ok(Self::TimeAttack),
+            x => Err(format!("Unknown play mode: {}", x)),
+        }
+    }
+}
+
+impl PlayMode {
+    pub fn repr(&self) -> &str {
+        match *self {
+            Self::Countdown => consts::COUNTDOWN_MODE_STRING_NAME,
+            Self::TimeAttack => consts::TIME_ATTACK_MODE_STRING_NAME,
+        }
+    }
 }
 
 pub struct GameFont {
-    font: Font, 
+    font: WeakFont, 
     spacing: f32,
 }
 
 pub struct Game {
     mode: GameMode,
     state: GameState,
-    difficulty: GameDifficulty,
+    is_first_launch: bool,
+    settings: Settings,
+    saved_settings: Settings,
+    overrides: SettingsOverrides,
+    config_path: PathBuf,
+    // Config file of a newer version is never written, so its unknown settings aren't lost
+    is_config_newer: bool,
+    session_seed: Option<u64>,
+    records: RecordTable,
+    daily_log: DailyLog,
     all_locales: Vec<Locale>,
     curr_locale_index: usize,
-    settings: GameSettings,
     game_font: GameFont,
+    locale_fonts: HashMap<String, GameFont>,
+    locales_modified_time: Vec<Option<SystemTime>>,
+    locales_checked_at: f64,
     window_width: f32,
     window_height: f32,
     fullscreen_width: i32,
@@ -76,86 +157,125 @@ pub struct Game {
 impl Game {
     pub const DEFAULT_WINDOW_WIDTH: i32 = 1600;
     pub const DEFAULT_WINDOW_HEIGHT: i32 = 900;
-    pub const CUSTOM_FONT_PATH: &str = "assets/fonts/Arimo-Regular.ttf";
-    pub const SAVE_CONFIG_PATH: &str = "game.cfg";
+    pub const CUSTOM_FONT_PATH: &str = consts::DEFAULT_FONT_PATH;
+    pub const SAVE_CONFIG_FILE: &str = "game.cfg";
+    pub const RECORDS_FILE: &str = "records.txt";
+    pub const DAILY_FILE: &str = "daily.txt";
+    pub const LOCALES_CHECK_INTERVAL: f64 = 0.5;
 
-    pub fn new(rl: &mut RaylibHandle, thread: &RaylibThread, mode: GameMode) -> Self {
-        let mut curr_locale_index: usize = 0;
-        let mut game_difficulty_int: i32 = 0;
-
-        let english_alphabet: &str = "abcdefghijklmnopqrstuvwxyzäöüßABCDEFGHIJKLMNOPQRSTUVWXYZÄÖÜẞ";
-        let cyrillic_alphabet: &str = "абвгдеєжзиіїйклмнопрстуфхцчшщьюяАБВГДЕЄЖЗИІЇЙКЛМНОПРСТУФХЦЧШЩЬЮЯ";
-        let ascii_symbols: &str = "1234567890 !@#$%^&*()_+-=[]{};':\",.<>/\\?`~";
-        let alphabet: String = format!("{}{}{}", ascii_symbols, english_alphabet, cyrillic_alphabet);
+    pub fn new(rl: &mut RaylibHandle, thread: &RaylibThread, all_locales: Vec<Locale>, mode: GameMode, config_path: Option<PathBuf>, overrides: SettingsOverrides) -> Result<Self, AssetError> {
         let mut has_to_load_locale_textures: bool = false;
+        let mut has_to_update_config: bool = false;
+        let mut is_config_newer: bool = false;
 
-        match File::open(Self::SAVE_CONFIG_PATH) {
-            Ok(file) => {
-                let reader = BufReader::new(file);
-
-                for line in reader.lines() {
-                    match line {
-                        Ok(x) => match x.split_once("=") {
-                            Some((param, value)) => {
-                                if param.starts_with("locale") {
-                                    curr_locale_index = value.trim().parse::<i32>().unwrap_or(0) as usize;
-                                } else if param.starts_with("difficulty") {
-                                    game_difficulty_int = value.trim().parse::<i32>().unwrap_or(0) as i32;
-                                }
-                            },
-                            None => {},
-                        },
-                        Err(_) => {},
-                    }
-                };
+        // Config file given on the command line is used as it is, without looking for the legacy one
+        let config_data: std::io::Result<String> = match &config_path {
+            Some(path) => std::fs::read_to_string(path),
+            None => paths::read_to_string(&paths::get_config_file(Self::SAVE_CONFIG_FILE), Self::SAVE_CONFIG_FILE),
+        };
+        let config_path: PathBuf = config_path.unwrap_or_else(|| paths::get_config_file(Self::SAVE_CONFIG_FILE));
+        let locale_codes: Vec<String> = all_locales.iter().map(|x| x.get_code().clone()).collect();
+
+        // Fallback chain: font declared by the locale, then the bundled font, then the one built into raylib.
+        // Atlas of every font holds only the characters its locales use
+        let mut locale_fonts: HashMap<String, GameFont> = HashMap::new();
+        for locale in all_locales.iter() {
+            if let Some(path) = locale.get_font_path() {
+                match Self::load_font(rl, thread, path, &locale.get_glyphs()) {
+                    Ok(font) => { locale_fonts.insert(locale.get_code().clone(), font); },
+                    Err(e) => eprintln!("{}, {} falls back to the default font", e, locale.get_language()),
+                }
+            }
+        }
+        let default_glyphs: String = translations::collect_glyphs(all_locales.iter()
+            .filter(|x| !locale_fonts.contains_key(x.get_code()))
+            .flat_map(|x| x.get_texts()));
+
+        let saved_settings: Settings = match config_data {
+            Ok(data) => {
+                let loaded: LoadedSettings = Settings::parse(&data, &locale_codes);
+                for error in loaded.errors.iter() {
+                    eprintln!("{}: {}", config_path.display(), error);
+                }
+                // Settings of the old format are written back in the current one
+                has_to_update_config = loaded.version < SETTINGS_VERSION;
+                is_config_newer = loaded.version > SETTINGS_VERSION;
+                loaded.settings
             },
             Err(_) => {
                 has_to_load_locale_textures = true;
+                Settings::default()
             },
-        }
+        };
+
+        let settings: Settings = overrides.apply(&saved_settings);
 
         let mut obj: Self = Self {
             mode: mode,
             state: GameState::Menu,
-            difficulty: match game_difficulty_int {
-                x if x == GameDifficulty::Hard as i32 => GameDifficulty::Hard,
-                x if x == GameDifficulty::Medium as i32 => GameDifficulty::Medium,
-                _ => GameDifficulty::Easy,
+            is_first_launch: has_to_load_locale_textures,
+            curr_locale_index: locale_codes.iter().position(|x| *x == settings.locale).unwrap_or(0),
+            settings: settings,
+            saved_settings: saved_settings,
+            overrides: overrides,
+            config_path: config_path,
+            is_config_newer: is_config_newer,
+            session_seed: None,
+            records: match paths::read_to_string(&paths::get_data_file(Self::RECORDS_FILE), Self::RECORDS_FILE) {
+                Ok(data) => RecordTable::parse(&data),
+                Err(_) => RecordTable::default(),
             },
-            all_locales: Locale::load("assets/locales/codes.xml").expect("Failed to load locales"),
-            curr_locale_index: curr_locale_index,
-            settings: GameSettings {
-                is_fullscreen: true,
-                is_vsync: true,
-                is_fps_visible: false,
+            daily_log: match std::fs::read_to_string(paths::get_data_file(Self::DAILY_FILE)) {
+                Ok(data) => DailyLog::parse(&data),
+                Err(_) => DailyLog::default(),
             },
-            game_font: GameFont {
-                font: rl.load_font_ex(&thread, Self::CUSTOM_FONT_PATH, 200, Some(alphabet.as_str())).unwrap(), 
-                spacing: match File::open(Self::CUSTOM_FONT_PATH) {
-                    Ok(_) => 1.0,
-                    Err(_) => 5.0,
-                }, 
+            locales_modified_time: all_locales.iter().map(|x| x.get_modified_time()).collect(),
+            all_locales: all_locales,
+            game_font: match Self::load_font(rl, thread, Self::CUSTOM_FONT_PATH, &default_glyphs) {
+                Ok(font) => font,
+                Err(e) => {
+                    eprintln!("{}, the default font is used instead", e);
+                    GameFont {
+                        font: rl.get_font_default(), 
+                        spacing: 5.0, 
+                    }
+                },
             },
+            locale_fonts: locale_fonts,
+            locales_checked_at: 0.0,
             window_width: Self::DEFAULT_WINDOW_WIDTH as f32,
             window_height: Self::DEFAULT_WINDOW_HEIGHT as f32,
             fullscreen_width: 0,
             fullscreen_height: 0,
         };
 
-        rl.set_window_title(thread, obj.get_locale().get(consts::GAME_TITLE_STRING_NAME).unwrap());
+        rl.set_window_title(thread, obj.get_locale().get(consts::GAME_TITLE_STRING_NAME));
+        if !obj.settings.is_vsync {
+            rl.clear_window_state(WindowState::default().set_vsync_hint(true));
+        }
         if has_to_load_locale_textures {
             for locale in obj.all_locales.iter_mut() {
-                locale.load_texture(rl, thread);
+                // Language without a flag is shown by its name
+                if let Err(e) = locale.load_texture(rl, thread) {
+                    eprintln!("{}", e);
+                }
             }
         }
+        if has_to_update_config {
+            obj.update_config_file();
+        }
 
-        obj
+        Ok(obj)
     }
 
     pub fn get_mode(&self) -> GameMode {
         self.mode
     }
 
+    pub fn is_first_launch(&self) -> bool {
+        self.is_first_launch
+    }
+
     pub fn get_state(&self) -> GameState {
         self.state
     }
@@ -165,7 +285,7 @@ impl Game {
     }
 
     pub fn get_difficulty(&self) -> GameDifficulty {
-        self.difficulty
+        self.settings.difficulty
     }
 
     pub fn get_all_locales(&self) -> &Vec<Locale> {
@@ -184,42 +304,166 @@ impl Game {
         for (index, locale) in self.all_locales.iter().enumerate() {
             if locale.get_code() == code {
                 self.curr_locale_index = index;
+                self.settings.locale = code.to_string();
+                self.overrides.locale = None;
             }
         }
         level.update_menu_btn_positions(self);
     }
 
-    pub fn change_locale(&mut self, level: &mut Level) {
-        self.curr_locale_index = if self.curr_locale_index + 1 >= self.all_locales.len() { 0 } else { self.curr_locale_index + 1 };
+    pub fn change_locale(&mut self, level: &mut Level, is_forward: bool) {
+        let locales_len: usize = self.all_locales.len();
+        self.curr_locale_index = if is_forward { (self.curr_locale_index + 1) % locales_len } else { (self.curr_locale_index + locales_len - 1) % locales_len };
+        self.settings.locale = self.get_locale().get_code().clone();
+        self.overrides.locale = None;
         self.update_config_file();
         level.update_menu_btn_positions(self);
     }
 
-    pub fn update_config_file(&self) {
-        // Update config file
-        let data: String = format!("locale = {}\ndifficulty = {}", self.curr_locale_index, self.get_difficulty() as i32);
-        std::fs::write(Self::SAVE_CONFIG_PATH, &data).expect("Unable to write save file");
+    pub fn update_config_file(&mut self) {
+        // Update config file, the game goes on with in-memory settings if it can't be written.
+        // Values overridden on the command line keep what was saved before
+        self.saved_settings = self.overrides.restore(&self.settings, &self.saved_settings);
+        if self.is_config_newer {
+            return;
+        }
+        if let Err(e) = paths::write(&self.config_path, &self.saved_settings.serialize()) {
+            eprintln!("Unable to write {}: {}", self.config_path.display(), e);
+        }
     }
 
-    pub fn get_settings(&self) -> &GameSettings {
+    pub fn get_settings(&self) -> &Settings {
         &self.settings
     }
 
-    pub fn change_difficulty(&mut self, difficulty: GameDifficulty) {
-        match difficulty {
-            GameDifficulty::Easy => self.difficulty = GameDifficulty::Medium,
-            GameDifficulty::Medium => self.difficulty = GameDifficulty::Hard,
-            GameDifficulty::Hard => self.difficulty = GameDifficulty::Easy,
+    pub fn change_difficulty(&mut self, difficulty: GameDifficulty, is_forward: bool) {
+        match (difficulty, is_forward) {
+            (GameDifficulty::Easy, true) | (GameDifficulty::Hard, false) => self.settings.difficulty = GameDifficulty::Medium,
+            (GameDifficulty::Medium, true) | (GameDifficulty::Easy, false) => self.settings.difficulty = GameDifficulty::Hard,
+            (GameDifficulty::Hard, true) | (GameDifficulty::Medium, false) => self.settings.difficulty = GameDifficulty::Easy,
         }
+        self.overrides.difficulty = None;
         self.update_config_file();
     }
 
-    pub fn get_font(&self) -> &Font {
-        &self.game_font.font
+    pub fn get_play_mode(&self) -> PlayMode {
+        self.settings.play_mode
+    }
+
+    pub fn change_play_mode(&mut self) {
+        // There are only two modes, so both directions lead to the other one
+        self.settings.play_mode = match self.settings.play_mode {
+            PlayMode::Countdown => PlayMode::TimeAttack,
+            PlayMode::TimeAttack => PlayMode::Countdown,
+        };
+        self.update_config_file();
+    }
+
+    pub fn get_board_size(&self) -> BoardSize {
+        self.settings.board_size.unwrap_or(self.settings.difficulty.get_board_size())
+    }
+
+    pub fn is_board_size_auto(&self) -> bool {
+        self.settings.board_size.is_none()
+    }
+
+    pub fn change_board_size(&mut self, is_forward: bool) {
+        // Cycles "auto" (size defined by difficulty) -> presets -> "auto"
+        let presets_len: usize = BoardSize::PRESETS.len();
+        self.settings.board_size = match (self.settings.board_size, is_forward) {
+            (None, true) => Some(BoardSize::PRESETS[0]),
+            (None, false) => Some(BoardSize::PRESETS[presets_len - 1]),
+            (Some(size), _) => match (BoardSize::PRESETS.iter().position(|&x| x == size), is_forward) {
+                (Some(index), true) if index + 1 < presets_len => Some(BoardSize::PRESETS[index + 1]),
+                (Some(index), false) if index > 0 => Some(BoardSize::PRESETS[index - 1]),
+                _ => None,
+            },
+        };
+        self.update_config_file();
+    }
+
+    pub fn get_sequence_rule(&self) -> SequenceRule {
+        self.settings.sequence_rule
+    }
+
+    pub fn change_sequence_rule(&mut self, is_forward: bool) {
+        self.settings.sequence_rule = if is_forward { self.settings.sequence_rule.next() } else { self.settings.sequence_rule.prev() };
+        self.update_config_file();
+    }
+
+    pub fn get_fixed_seed(&self) -> Option<u64> {
+        // Seed from the command line has priority over the one from config file
+        self.session_seed.or(self.settings.seed)
+    }
+
+    pub fn set_session_seed(&mut self, seed: Option<u64>) {
+        self.session_seed = seed;
+    }
+
+    pub fn is_daily_completed(&self) -> bool {
+        self.get_daily_attempt(daily::get_today()).is_some()
+    }
+
+    pub fn get_daily_attempt(&self, date: NaiveDate) -> Option<DailyAttempt> {
+        self.daily_log.get(date)
+    }
+
+    pub fn start_daily(&mut self, date: NaiveDate) {
+        if self.daily_log.start(date) {
+            self.update_daily_file();
+        }
+    }
+
+    pub fn finish_daily(&mut self, date: NaiveDate, record: Record) {
+        self.daily_log.finish(date, record);
+        self.update_daily_file();
+    }
+
+    fn update_daily_file(&self) {
+        // Debug runs are kept out of the saved daily results, like out of the records
+        if self.mode == GameMode::Debug {
+            return;
+        }
+        let path: PathBuf = paths::get_data_file(Self::DAILY_FILE);
+        if let Err(e) = paths::write(&path, &self.daily_log.serialize()) {
+            eprintln!("Unable to write {}: {}", path.display(), e);
+        }
+    }
+
+    pub fn get_records(&self) -> &RecordTable {
+        &self.records
+    }
+
+    pub fn add_record(&mut self, key: RecordKey, record: Record) -> Option<usize> {
+        let place: Option<usize> = self.records.add_run(self.mode, key, record);
+        if place.is_some() {
+            let path: PathBuf = paths::get_data_file(Self::RECORDS_FILE);
+            if let Err(e) = paths::write(&path, &self.records.serialize()) {
+                eprintln!("Unable to write {}: {}", path.display(), e);
+            }
+        }
+        place
+    }
+
+    fn load_font(rl: &mut RaylibHandle, thread: &RaylibThread, path: &str, glyphs: &str) -> Result<GameFont, AssetError> {
+        // Font is loaded once and lives until the game is closed
+        let font: Font = assets::load_font(rl, thread, path, 200, glyphs)?;
+        Ok(GameFont {
+            font: font.make_weak(), 
+            spacing: 1.0, 
+        })
+    }
+
+    fn get_game_font(&self) -> &GameFont {
+        self.locale_fonts.get(self.get_locale().get_code()).unwrap_or(&self.game_font)
+    }
+
+    pub fn get_font(&self) -> &WeakFont {
+        &self.get_game_font().font
     }
 
     pub fn get_font_spacing(&self) -> f32 {
-        self.game_font.spacing
+        self.get_game_font().spacing
     }
 
     pub fn get_window_width(&self) -> f32 {
@@ -242,10 +486,12 @@ impl Game {
 
     pub fn toggle_fps_monitor(&mut self) {
         self.settings.is_fps_visible = !self.settings.is_fps_visible;
+        self.update_config_file();
     }
 
     pub fn toggle_fullscreen(&mut self, rl: &mut RaylibHandle, menu: &mut Menu, level: &mut Level) {
         self.settings.is_fullscreen = !self.settings.is_fullscreen;
+        self.overrides.is_fullscreen = None;
 
         // Toggling fullscreen with borderless window mode requires order
         if self.settings.is_fullscreen {
@@ -263,9 +509,11 @@ impl Game {
         // Recalculate menu buttons positions
         menu.update_btn_positions(self);
         level.update_btn_positions(self);
+
+        self.update_config_file();
     }
 
-    pub fn process_controller(&mut self, rl: &mut RaylibHandle, menu: &mut Menu, level: &mut Level) {
+    pub fn process_controller(&mut self, rl: &mut RaylibHandle, input: &Input, menu: &mut Menu, level: &mut Level) {
         rl.set_exit_key(None);
 
         if rl.is_window_resized() {
@@ -279,8 +527,13 @@ impl Game {
             self.toggle_fullscreen(rl, menu, level);
         }
 
-        if rl.is_key_released(KEY_ESCAPE) {
-            if menu.get_state() == MenuState::Help {
+        if self.mode == GameMode::Debug {
+            self.reload_changed_locales(rl, menu, level);
+        }
+
+        // Esc and gamepad B
+        if input.is_triggered(Action::Back) {
+            if menu.get_state() == MenuState::Help || menu.get_state() == MenuState::Records || menu.get_state() == MenuState::Settings {
                 menu.set_state(MenuState::Primary);
             } else {
                 match self.state {
@@ -289,6 +542,50 @@ impl Game {
                 }
             }
         }
+
+        // Gamepad Start pauses the game and continues it from any menu screen
+        if input.is_triggered(Action::Pause) {
+            match self.state {
+                GameState::Game => self.state = GameState::Menu,
+                GameState::Menu if level.is_started() && !level.is_over() => {
+                    menu.set_state(MenuState::Primary);
+                    level.resume(self);
+                },
+                _ => {},
+            }
+        }
+    }
+
+    fn reload_changed_locales(&mut self, rl: &RaylibHandle, menu: &mut Menu, level: &mut Level) {
+        // Translators see their changes without restarting the game, files are polled as there are only a few of them
+        if rl.get_time() - self.locales_checked_at < Self::LOCALES_CHECK_INTERVAL {
+            return;
+        }
+        self.locales_checked_at = rl.get_time();
+
+        let mut is_reloaded: bool = false;
+        for (index, locale) in self.all_locales.iter_mut().enumerate() {
+            let modified_time: Option<SystemTime> = locale.get_modified_time();
+            if modified_time == self.locales_modified_time[index] {
+                continue;
+            }
+            self.locales_modified_time[index] = modified_time;
+
+            match locale.reload() {
+                Ok(_) => {
+                    eprintln!("Locale \"{}\" is reloaded", locale.get_code());
+                    is_reloaded = true;
+                },
+                Err(e) => eprintln!("{}", e),
+            }
+        }
+
+        if is_reloaded {
+            // Characters which are new to the font atlas are shown only after restart
+            Locale::apply_fallback(&mut self.all_locales);
+            menu.update_btn_positions(self);
+            level.update_menu_btn_positions(self);
+        }
     }
 
     fn draw_fps(&self, d: &mut RaylibDrawHandle) {
@@ -297,7 +594,19 @@ impl Game {
         }
     }
 
+    fn draw_missing_keys(&self, d: &mut RaylibDrawHandle) {
+        // Translators see which strings of the current language are taken from the base locale
+        let missing_keys: &Vec<String> = self.get_locale().get_missing_keys();
+        if self.mode != GameMode::Debug || missing_keys.is_empty() {
+            return;
+        }
+
+        let text: String = format!("Missing in \"{}\": {}", self.get_locale().get_code(), missing_keys.join(", "));
+        d.draw_text(&text, 10, self.window_height as i32 - 30, 20, Color::RED);
+    }
+
     pub fn draw(&self, d: &mut RaylibDrawHandle) {
         self.draw_fps(d);
+        self.draw_missing_keys(d);
     }
 }
\ No newline at end of file
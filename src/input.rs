@@ -0,0 +1,181 @@
+use raylib::prelude::*;
+use raylib::consts::KeyboardKey::*;
+use raylib::consts::GamepadButton::*;
+use raylib::consts::GamepadAxis::*;
+
+const GAMEPAD_INDEX: i32 = 0;
+const STICK_DEADZONE: f32 = 0.5;
+// Held stick repeats the move like a held key does
+const STICK_REPEAT_DELAY_SECS: f64 = 0.4;
+const STICK_REPEAT_INTERVAL_SECS: f64 = 0.12;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum InputDevice {
+    #[default]
+    Mouse,
+    Keyboard,
+    Gamepad,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Action {
+    Up,
+    Down,
+    Left,
+    Right,
+    Confirm,
+    Back,
+    Pause,
+}
+
+#[derive(Default)]
+struct StickRepeat {
+    action: Option<Action>,
+    repeat_at: f64,
+}
+
+impl StickRepeat {
+    // Returns the action of the stick when it is pushed, then again after the delay and every interval while it is held
+    fn update(&mut self, action: Option<Action>, current_time: f64) -> Option<Action> {
+        let is_triggered: bool = match action {
+            None => false,
+            Some(_) if action != self.action => {
+                self.repeat_at = current_time + STICK_REPEAT_DELAY_SECS;
+                true
+            },
+            Some(_) if current_time >= self.repeat_at => {
+                self.repeat_at = current_time + STICK_REPEAT_INTERVAL_SECS;
+                true
+            },
+            Some(_) => false,
+        };
+        self.action = action;
+        if is_triggered { action } else { None }
+    }
+}
+
+// Keyboard and gamepad controls shared by menus and the board, collected once per frame
+#[derive(Default)]
+pub struct Input {
+    device: InputDevice,
+    actions: Vec<Action>,
+    stick: StickRepeat,
+}
+
+impl Input {
+    const KEYS: [(KeyboardKey, Action); 7] = [
+        (KEY_UP, Action::Up),
+        (KEY_DOWN, Action::Down),
+        (KEY_LEFT, Action::Left),
+        (KEY_RIGHT, Action::Right),
+        (KEY_ENTER, Action::Confirm),
+        (KEY_SPACE, Action::Confirm),
+        (KEY_ESCAPE, Action::Back),
+    ];
+    // Other keys of the board, they only switch the device to the keyboard
+    const BOARD_KEYS: [KeyboardKey; 26] = [
+        KEY_W, KEY_A, KEY_S, KEY_D, KEY_TAB, KEY_BACKSPACE,
+        KEY_ZERO, KEY_ONE, KEY_TWO, KEY_THREE, KEY_FOUR, KEY_FIVE, KEY_SIX, KEY_SEVEN, KEY_EIGHT, KEY_NINE,
+        KEY_KP_0, KEY_KP_1, KEY_KP_2, KEY_KP_3, KEY_KP_4, KEY_KP_5, KEY_KP_6, KEY_KP_7, KEY_KP_8, KEY_KP_9,
+    ];
+    const BUTTONS: [(GamepadButton, Action); 7] = [
+        (GAMEPAD_BUTTON_LEFT_FACE_UP, Action::Up),
+        (GAMEPAD_BUTTON_LEFT_FACE_DOWN, Action::Down),
+        (GAMEPAD_BUTTON_LEFT_FACE_LEFT, Action::Left),
+        (GAMEPAD_BUTTON_LEFT_FACE_RIGHT, Action::Right),
+        (GAMEPAD_BUTTON_RIGHT_FACE_DOWN, Action::Confirm),
+        (GAMEPAD_BUTTON_RIGHT_FACE_RIGHT, Action::Back),
+        (GAMEPAD_BUTTON_MIDDLE_RIGHT, Action::Pause),
+    ];
+
+    pub fn update(&mut self, rl: &mut RaylibHandle) {
+        self.actions.clear();
+
+        // The last used device decides whether the mouse hover or the focus frame is shown
+        let mouse_delta: Vector2 = rl.get_mouse_delta();
+        if mouse_delta.x != 0.0 || mouse_delta.y != 0.0 || rl.is_mouse_button_pressed(MouseButton::MOUSE_BUTTON_LEFT) {
+            self.device = InputDevice::Mouse;
+        }
+        // Keys are checked one by one, so the queue of pressed keys stays for the screens that read it
+        if Self::KEYS.iter().map(|(key, _)| *key).chain(Self::BOARD_KEYS).any(|x| rl.is_key_pressed(x)) {
+            self.device = InputDevice::Keyboard;
+        }
+
+        for (key, action) in Self::KEYS {
+            // Esc is handled on release as before, so it doesn't leak into the screen it leads to.
+            // Only moves repeat while the key is held
+            let is_triggered: bool = match action {
+                Action::Back => rl.is_key_released(key),
+                Action::Confirm => rl.is_key_pressed(key),
+                _ => rl.is_key_pressed(key) || rl.is_key_pressed_repeat(key),
+            };
+            if is_triggered {
+                self.actions.push(action);
+            }
+        }
+
+        if !rl.is_gamepad_available(GAMEPAD_INDEX) {
+            self.stick = StickRepeat::default();
+            return;
+        }
+
+        for (button, action) in Self::BUTTONS {
+            if rl.is_gamepad_button_pressed(GAMEPAD_INDEX, button) {
+                self.actions.push(action);
+                self.device = InputDevice::Gamepad;
+            }
+        }
+
+        let stick_action: Option<Action> = Self::get_stick_action(rl);
+        if let Some(action) = self.stick.update(stick_action, rl.get_time()) {
+            self.actions.push(action);
+            self.device = InputDevice::Gamepad;
+        }
+    }
+
+    fn get_stick_action(rl: &RaylibHandle) -> Option<Action> {
+        // Only the stronger axis counts, so a diagonal push moves in one direction
+        let x: f32 = rl.get_gamepad_axis_movement(GAMEPAD_INDEX, GAMEPAD_AXIS_LEFT_X);
+        let y: f32 = rl.get_gamepad_axis_movement(GAMEPAD_INDEX, GAMEPAD_AXIS_LEFT_Y);
+
+        if x.abs().max(y.abs()) < STICK_DEADZONE {
+            None
+        } else if x.abs() > y.abs() {
+            Some(if x > 0.0 { Action::Right } else { Action::Left })
+        } else {
+            Some(if y > 0.0 { Action::Down } else { Action::Up })
+        }
+    }
+
+    pub fn is_triggered(&self, action: Action) -> bool {
+        self.actions.contains(&action)
+    }
+
+    pub fn is_mouse_used(&self) -> bool {
+        self.device == InputDevice::Mouse
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeats_held_stick() {
+        let mut stick: StickRepeat = StickRepeat::default();
+        let repeat_at: f64 = 1.0 + STICK_REPEAT_DELAY_SECS + 0.01;
+
+        assert_eq!(stick.update(Some(Action::Up), 1.0), Some(Action::Up));
+        assert_eq!(stick.update(Some(Action::Up), 1.0 + STICK_REPEAT_DELAY_SECS / 2.0), None);
+        assert_eq!(stick.update(Some(Action::Up), repeat_at), Some(Action::Up));
+        assert_eq!(stick.update(Some(Action::Up), repeat_at + STICK_REPEAT_INTERVAL_SECS / 2.0), None);
+        assert_eq!(stick.update(Some(Action::Up), repeat_at + STICK_REPEAT_INTERVAL_SECS), Some(Action::Up));
+
+        // New direction and a push after release start over with the delay
+        assert_eq!(stick.update(Some(Action::Left), repeat_at + 0.2), Some(Action::Left));
+        assert_eq!(stick.update(Some(Action::Left), repeat_at + 0.3), None);
+        assert_eq!(stick.update(None, repeat_at + 0.35), None);
+        assert_eq!(stick.update(Some(Action::Left), repeat_at + 0.4), Some(Action::Left));
+        assert_eq!(stick.update(Some(Action::Left), repeat_at + 0.5), None);
+    }
+}
@@ -1,10 +1,19 @@
 use raylib::prelude::*;
 use raylib::consts::MouseButton::*;
+use raylib::consts::KeyboardKey::*;
+use chrono::NaiveDate;
 
+use crate::board::{ Board, BoardSize, ClickResult, Outcome, TileColor };
 use crate::consts;
+use crate::daily::{ self, DailyRun };
 use crate::game;
+use crate::input::{ Action, Input };
+use crate::records::{ Record, RecordKey };
+use crate::rng::generate_seed;
+use crate::scoring::{ self, ScoreBreakdown, ScoringModel };
+use crate::sequence::SequenceRule;
 use crate::timer;
-use crate::utils::{ generate_numbers_array, draw_text_center, Button };
+use crate::utils::{ draw_text_center, Button, FOCUS_COLOR, FOCUS_FRAME_WIDTH };
 
 const RECTANGLE_WIDTH: f32 = 100.0;
 const RECTANGLE_HEIGHT: f32 = 60.0;
@@ -12,20 +21,28 @@ const MAX_H_OPACITY: f32 = 100.0;
 const MIN_H_OPACITY: f32 = 20.0;
 const MAX_V_OPACITY: f32 = 100.0;
 const MIN_V_OPACITY: f32 = 20.0;
-const H_COUNT: i32 = 8;
-const V_COUNT: i32 = 7;
+const BOARD_OPACITY_PADDING: f32 = 600.0;
+const BOARD_RESERVED_SPACE: f32 = 300.0;
 
 const BTN_TEXT_FONTSIZE: f32 = 48.0;
+// Typed digits are forgotten after a pause, so the next number starts from scratch
+const TYPING_RESET_SECS: f64 = 1.5;
 
 pub struct Level {
-    numbers: Vec<i32>,
+    board: Board,
+    board_size: BoardSize,
+    difficulty: game::GameDifficulty,
+    play_mode: game::PlayMode,
+    daily_run: Option<DailyRun>,
     buttons: Vec<Rectangle>,
+    tile_font_size: f32,
     active_btn_index: i32,
-    incorrect_btn_index: i32,
-    correct_buttons: Vec<i32>,
-    score: i32,
-    fails: i32,
+    cursor_index: Option<usize>,
+    typed_number: String,
+    typed_at: f64,
     timer: timer::Timer,
+    scoring: Box<dyn ScoringModel>,
+    record_place: Option<usize>,
     btn_game_exit: Button,
     btn_after_game_try_again: Button,
     btn_after_game_exit: Button,
@@ -35,20 +52,22 @@ impl Level {
     pub fn new(game: &game::Game) -> Self {
         let window_width: f32 = game.get_window_width();
         let window_height: f32 = game.get_window_height();
-        let mut h_opacity: f32 = (window_width - 600.0 - H_COUNT as f32 * RECTANGLE_WIDTH) / (H_COUNT - 1) as f32;
-        let mut v_opacity: f32 = (window_height - 600.0 - V_COUNT as f32 * RECTANGLE_HEIGHT) / (V_COUNT - 1) as f32;
-        if h_opacity > MAX_H_OPACITY { h_opacity = MAX_H_OPACITY; }
-        if v_opacity > MAX_V_OPACITY { v_opacity = MAX_V_OPACITY; }
 
         let mut obj: Self = Self {
-            numbers: Vec::with_capacity((H_COUNT * V_COUNT) as usize),
+            board: Board::default(),
+            board_size: game.get_board_size(),
+            difficulty: game.get_difficulty(),
+            play_mode: game.get_play_mode(),
+            daily_run: None,
             buttons: Vec::new(),
+            tile_font_size: BTN_TEXT_FONTSIZE,
             active_btn_index: -1,
-            incorrect_btn_index: -1,
-            correct_buttons: Vec::new(),
-            score: 0,
-            fails: 0,
-            timer: timer::Timer::new(Self::get_timer_duration(game)),
+            cursor_index: None,
+            typed_number: String::new(),
+            typed_at: 0.0,
+            timer: timer::Timer::new(Self::get_timer_duration(game, game.get_difficulty())),
+            scoring: scoring::default_model(),
+            record_place: None,
             btn_game_exit: Button::new(Rectangle {
                 x: window_width - 150.0 - 10.0, 
                 y: 80.0, 
@@ -69,29 +88,20 @@ impl Level {
             }, consts::EXIT_BTN_STRING_NAME, Color::WHITE),
         };
 
-        for v_index in 0..V_COUNT {
-            for h_index in 0..H_COUNT {
-                obj.buttons.push(Rectangle::new(
-                    h_index as f32 * (RECTANGLE_WIDTH + h_opacity) + (window_width - H_COUNT as f32 * (RECTANGLE_WIDTH + h_opacity) + h_opacity) / 2.0, 
-                    v_index as f32 * (RECTANGLE_HEIGHT + v_opacity) + (window_height - V_COUNT as f32 * (RECTANGLE_HEIGHT + v_opacity) + v_opacity) / 2.0, 
-                    RECTANGLE_WIDTH, 
-                    RECTANGLE_HEIGHT
-                ));
-            }
-        }
+        obj.update_btn_positions(game);
         
         obj
     }
 
-    fn get_timer_duration(game: &game::Game) -> i32 {
+    fn get_timer_duration(game: &game::Game, difficulty: game::GameDifficulty) -> i32 {
         if game.get_mode() == game::GameMode::Release {
-            match game.get_difficulty() {
+            match difficulty {
                 game::GameDifficulty::Easy => 3 * 60,
                 game::GameDifficulty::Medium => 2 * 60,
                 game::GameDifficulty::Hard => 2 * 60,
             }
         } else if game.get_mode() == game::GameMode::Debug {
-            match game.get_difficulty() {
+            match difficulty {
                 game::GameDifficulty::Easy => 3 * 60,
                 game::GameDifficulty::Medium => 60,
                 game::GameDifficulty::Hard => 10,
@@ -100,7 +110,7 @@ impl Level {
     }
 
     pub fn is_started(&self) -> bool {
-        self.numbers.len() > 0
+        !self.board.is_empty()
     }
 
     pub fn is_over(&self) -> bool {
@@ -113,31 +123,103 @@ impl Level {
     }
 
     pub fn start(&mut self, game: &game::Game) {
-        self.numbers = generate_numbers_array(H_COUNT * V_COUNT);
+        self.daily_run = None;
+        self.start_board(game, game.get_board_size(), game.get_difficulty(), game.get_play_mode(), game.get_sequence_rule(), game.get_fixed_seed().unwrap_or_else(generate_seed));
+    }
+
+    pub fn start_daily(&mut self, game: &mut game::Game) {
+        let date: NaiveDate = daily::get_today();
+        let is_practice: bool = game.is_daily_completed();
+
+        // The attempt is used as soon as the board is shown, later runs of the same day are practice only
+        if !is_practice {
+            game.start_daily(date);
+        }
+        self.daily_run = Some(DailyRun {
+            date: date,
+            is_practice: is_practice,
+        });
+        self.start_board(game, daily::BOARD_SIZE, daily::DIFFICULTY, daily::PLAY_MODE, daily::SEQUENCE_RULE, daily::get_seed(date));
+    }
+
+    fn start_board(&mut self, game: &game::Game, board_size: BoardSize, difficulty: game::GameDifficulty, play_mode: game::PlayMode, rule: SequenceRule, seed: u64) {
+        self.board_size = board_size;
+        self.difficulty = difficulty;
+        self.play_mode = play_mode;
+        self.board = Board::generate(self.board_size, seed, rule);
+        self.update_btn_positions(game);
         self.restart(game);
     }
 
     pub fn restart(&mut self, game: &game::Game) {
         self.active_btn_index = -1;
-        self.incorrect_btn_index = -1;
-        self.correct_buttons.clear();
-        self.score = 0;
-        self.fails = 0;
-        self.timer = timer::Timer::new(Self::get_timer_duration(game));
+        self.cursor_index = None;
+        self.typed_number.clear();
+        self.board.reset();
+        self.scoring.reset();
+        self.record_place = None;
+        self.timer = match self.play_mode {
+            game::PlayMode::Countdown => timer::Timer::new(Self::get_timer_duration(game, self.difficulty)),
+            game::PlayMode::TimeAttack => timer::Timer::new_stopwatch(),
+        };
         self.timer.start();
     }
+
+    fn get_time_rules(&self) -> timer::TimeRules {
+        let mut rules: timer::TimeRules = self.difficulty.get_time_rules();
+
+        // Bonuses would let the stopwatch run backwards, so time attack only adds penalties
+        if self.play_mode == game::PlayMode::TimeAttack {
+            rules.correct_click_bonus = 0.0;
+        }
+        rules
+    }
+
+    fn can_try_again(&self) -> bool {
+        // Scored daily attempt can't be replayed
+        match self.daily_run {
+            Some(run) => run.is_practice,
+            None => true,
+        }
+    }
     
-    pub fn process_controller(&mut self, rl: &RaylibHandle, game: &mut game::Game) {
-        let mouse_pos: Vector2 = rl.get_mouse_position();
+    fn save_record(&mut self, game: &mut game::Game) {
+        let breakdown: ScoreBreakdown = self.scoring.get_breakdown();
+        let record: Record = Record {
+            score: breakdown.get_total(),
+            fails: breakdown.fails,
+            time: self.timer.get_elapsed_time(),
+            seed: self.board.get_seed(),
+            date: daily::get_today(),
+        };
+
+        // Daily challenge has its own results, practice runs of it are not saved at all
+        if let Some(run) = self.daily_run {
+            if !run.is_practice {
+                game.finish_daily(run.date, record);
+            }
+            return;
+        }
+
+        let key: RecordKey = RecordKey {
+            play_mode: self.play_mode,
+            difficulty: self.difficulty,
+            board_size: self.board_size,
+        };
+        self.record_place = game.add_record(key, record);
+    }
+
+    pub fn process_controller(&mut self, rl: &mut RaylibHandle, input: &Input, game: &mut game::Game) {
+        // Hover is shown only while the mouse is the last used device, otherwise the cursor is
+        let mouse_pos: Option<Vector2> = if input.is_mouse_used() { Some(rl.get_mouse_position()) } else { None };
 
         if game.get_state() == game::GameState::Menu && self.is_started() {
             self.timer.pause();
         }
         if game.get_state() == game::GameState::Game {
             let mut has_collision: bool = false;
-            let mut index: i32;
 
-            if self.btn_game_exit.get_rec().check_collision_point_rec(mouse_pos) {
+            if mouse_pos.is_some_and(|x| self.btn_game_exit.get_rec().check_collision_point_rec(x)) {
                 self.btn_game_exit.set_color(Color::LIGHTGREEN);
                 if rl.is_mouse_button_released(MOUSE_BUTTON_LEFT) {
                     game.set_state(game::GameState::Menu);
@@ -147,41 +229,44 @@ impl Level {
                 self.btn_game_exit.set_color(Color::WHITE);
             }
 
-            if self.correct_buttons.len() == (H_COUNT * V_COUNT) as usize {
-                game.set_state(game::GameState::Win);
-                self.timer.finish();
-            }
-            if self.timer.is_over() {
-                game.set_state(game::GameState::Lose);
-                self.timer.finish();
+            match self.board.get_outcome(self.timer.is_over()) {
+                Some(Outcome::Win) => {
+                    game.set_state(game::GameState::Win);
+                    self.timer.finish();
+                    self.save_record(game);
+                },
+                Some(Outcome::Lose) => {
+                    game.set_state(game::GameState::Lose);
+                    self.timer.finish();
+                    self.save_record(game);
+                },
+                None => {},
             }
 
             if self.timer.is_active() {
+                let mut clicked_index: Option<usize> = None;
                 for (i, el) in self.buttons.iter().enumerate() {
-                    index = i as i32;
-                    if self.correct_buttons.contains(&index) {
+                    if self.board.is_cleared(i) {
                         continue;
                     }
 
-                    if el.check_collision_point_rec(mouse_pos) {
+                    if mouse_pos.is_some_and(|x| el.check_collision_point_rec(x)) {
                         has_collision = true;
-                        self.active_btn_index = index;
+                        self.active_btn_index = i as i32;
                         if rl.is_mouse_button_released(MOUSE_BUTTON_LEFT) {
-                            if self.numbers[i] == self.correct_buttons.len() as i32 + 1 {
-                                self.correct_buttons.push(index);
-                                self.incorrect_btn_index = -1;
-                                self.score += 1;
-                            } else {
-                                if self.incorrect_btn_index != index {
-                                    self.fails += 1;
-                                }
-                                self.incorrect_btn_index = index;
-                            }
-                            self.active_btn_index = -1;
+                            clicked_index = Some(i);
                         }
                         break;
                     }
                 }
+                // Keyboard and gamepad pick tiles by the grid cursor or by the typed number, then resolve them as a click
+                if let Some(index) = self.process_keyboard(rl, input) {
+                    clicked_index = Some(index);
+                }
+                if let Some(index) = clicked_index {
+                    self.click_tile(index);
+                    self.active_btn_index = -1;
+                }
                 if !has_collision {
                     self.active_btn_index = -1;
                 }
@@ -189,7 +274,13 @@ impl Level {
                 self.timer.activate();
             }
         } else if game.get_state() == game::GameState::Win || game.get_state() == game::GameState::Lose {
-            if self.btn_after_game_try_again.get_rec().check_collision_point_rec(mouse_pos) {
+            // Confirm of the keyboard or gamepad tries again, back leads to the menu as Esc does
+            if !self.can_try_again() {
+                self.btn_after_game_try_again.set_color(Color::WHITE);
+            } else if input.is_triggered(Action::Confirm) {
+                game.set_state(game::GameState::Game);
+                self.restart(game);
+            } else if mouse_pos.is_some_and(|x| self.btn_after_game_try_again.get_rec().check_collision_point_rec(x)) {
                 self.btn_after_game_try_again.set_color(Color::LIGHTGREEN);
                 if rl.is_mouse_button_released(MOUSE_BUTTON_LEFT) {
                     self.btn_after_game_try_again.set_color(Color::WHITE);
@@ -200,7 +291,7 @@ impl Level {
                 self.btn_after_game_try_again.set_color(Color::WHITE);
             }
 
-            if self.btn_after_game_exit.get_rec().check_collision_point_rec(mouse_pos) {
+            if mouse_pos.is_some_and(|x| self.btn_after_game_exit.get_rec().check_collision_point_rec(x)) {
                 self.btn_after_game_exit.set_color(Color::LIGHTGREEN);
                 if rl.is_mouse_button_released(MOUSE_BUTTON_LEFT) {
                     game.set_state(game::GameState::Menu);
@@ -212,12 +303,123 @@ impl Level {
         }
     }
 
+    fn process_keyboard(&mut self, rl: &mut RaylibHandle, input: &Input) -> Option<usize> {
+        // Mouse takes over as soon as it's used, so the cursor and the hover don't point at different tiles
+        if input.is_mouse_used() {
+            self.cursor_index = None;
+        }
+        if !self.typed_number.is_empty() && rl.get_time() - self.typed_at > TYPING_RESET_SECS {
+            self.typed_number.clear();
+        }
+
+        // Arrows, D-pad and stick come as actions, WASD is read here as it's used only on the board
+        let moves: [(Action, KeyboardKey, i32, i32); 4] = [
+            (Action::Left, KEY_A, -1, 0),
+            (Action::Right, KEY_D, 1, 0),
+            (Action::Up, KEY_W, 0, -1),
+            (Action::Down, KEY_S, 0, 1),
+        ];
+        for (action, key, columns_delta, rows_delta) in moves {
+            if input.is_triggered(action) || rl.is_key_pressed(key) || rl.is_key_pressed_repeat(key) {
+                self.cursor_index = Some(match self.cursor_index {
+                    Some(index) => self.board_size.move_index(index, columns_delta, rows_delta),
+                    None => 0,
+                });
+                self.typed_number.clear();
+            }
+        }
+
+        while let Some(c) = rl.get_char_pressed() {
+            if c.is_ascii_digit() {
+                self.type_digit(c);
+                self.typed_at = rl.get_time();
+            }
+        }
+        if rl.is_key_pressed(KEY_BACKSPACE) && self.typed_number.pop().is_some() {
+            self.typed_at = rl.get_time();
+            if let Some(index) = self.board.find_number(&self.typed_number, None) {
+                self.cursor_index = Some(index);
+            }
+        }
+        // Tab switches between tiles with the same number, as red and black tables have two of each
+        if rl.is_key_pressed(KEY_TAB) && !self.typed_number.is_empty() {
+            if let Some(index) = self.board.find_number(&self.typed_number, self.cursor_index) {
+                self.cursor_index = Some(index);
+            }
+        }
+
+        if input.is_triggered(Action::Confirm) {
+            self.typed_number.clear();
+            return self.cursor_index;
+        }
+        None
+    }
+
+    fn type_digit(&mut self, digit: char) {
+        self.typed_number.push(digit);
+
+        // Digit which doesn't continue any number on the board starts a new one
+        if !self.board.has_number_prefix(&self.typed_number) {
+            self.typed_number = digit.to_string();
+        }
+        if let Some(index) = self.board.find_number(&self.typed_number, None) {
+            self.cursor_index = Some(index);
+        }
+    }
+
+    fn click_tile(&mut self, index: usize) {
+        let rules: timer::TimeRules = self.get_time_rules();
+        match self.board.click(index) {
+            ClickResult::Correct => {
+                self.scoring.on_correct(self.timer.get_elapsed_time());
+                self.timer.add_time(rules.correct_click_bonus);
+            },
+            ClickResult::Wrong => {
+                // Penalty of a countdown takes away the time left and doesn't change the elapsed time
+                let penalty: f64 = if self.play_mode == game::PlayMode::TimeAttack { rules.wrong_click_penalty } else { 0.0 };
+                self.scoring.on_wrong(self.timer.get_elapsed_time(), penalty);
+                self.timer.add_time(-rules.wrong_click_penalty);
+            },
+            _ => {},
+        }
+    }
+
+    // Debug console clears tiles the same way as clicks do, so the score shows what the clicks would give
+    pub fn solve(&mut self, count: usize) -> usize {
+        let solution: Vec<usize> = self.board.get_solution();
+        for index in solution.iter().take(count) {
+            self.click_tile(*index);
+        }
+        count.min(solution.len())
+    }
+
+    pub fn set_time(&mut self, secs: f64) {
+        self.timer.set_time(secs);
+    }
+
+    pub fn get_board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn get_timer(&self) -> &timer::Timer {
+        &self.timer
+    }
+
+    pub fn get_tile_recs(&self) -> &Vec<Rectangle> {
+        &self.buttons
+    }
+
     pub fn update_btn_positions(&mut self, game: &game::Game) {
         let window_width: f32 = game.get_window_width();
         let window_height: f32 = game.get_window_height();
-        let mut h_opacity: f32 = (window_width - 600.0 - H_COUNT as f32 * RECTANGLE_WIDTH) / (H_COUNT - 1) as f32;
-        let mut v_opacity: f32 = (window_height - 600.0 - V_COUNT as f32 * RECTANGLE_HEIGHT) / (V_COUNT - 1) as f32;
-        let mut index: usize;
+        let h_count: i32 = self.board_size.columns;
+        let v_count: i32 = self.board_size.rows;
+
+        // Tiles shrink when the board doesn't fit into the window even with minimal gaps between them
+        let tile_width: f32 = RECTANGLE_WIDTH.min((window_width - BOARD_RESERVED_SPACE - (h_count - 1) as f32 * MIN_H_OPACITY) / h_count as f32);
+        let tile_height: f32 = RECTANGLE_HEIGHT.min((window_height - BOARD_RESERVED_SPACE - (v_count - 1) as f32 * MIN_V_OPACITY) / v_count as f32);
+        let mut h_opacity: f32 = (window_width - BOARD_OPACITY_PADDING - h_count as f32 * tile_width) / (h_count - 1) as f32;
+        let mut v_opacity: f32 = (window_height - BOARD_OPACITY_PADDING - v_count as f32 * tile_height) / (v_count - 1) as f32;
 
         if h_opacity > MAX_H_OPACITY {
             h_opacity = MAX_H_OPACITY;
@@ -230,11 +432,16 @@ impl Level {
             v_opacity = MIN_V_OPACITY;
         }
 
-        for v_index in 0..V_COUNT {
-            for h_index in 0..H_COUNT {
-                index = (v_index * H_COUNT + h_index) as usize;
-                self.buttons[index].x = h_index as f32 * (RECTANGLE_WIDTH + h_opacity) + (window_width - H_COUNT as f32 * (RECTANGLE_WIDTH + h_opacity) + h_opacity) / 2.0;
-                self.buttons[index].y = v_index as f32 * (RECTANGLE_HEIGHT + v_opacity) + (window_height - V_COUNT as f32 * (RECTANGLE_HEIGHT + v_opacity) + v_opacity) / 2.0;
+        self.tile_font_size = BTN_TEXT_FONTSIZE * (tile_width / RECTANGLE_WIDTH).min(tile_height / RECTANGLE_HEIGHT);
+        self.buttons.clear();
+        for v_index in 0..v_count {
+            for h_index in 0..h_count {
+                self.buttons.push(Rectangle::new(
+                    h_index as f32 * (tile_width + h_opacity) + (window_width - h_count as f32 * (tile_width + h_opacity) + h_opacity) / 2.0, 
+                    v_index as f32 * (tile_height + v_opacity) + (window_height - v_count as f32 * (tile_height + v_opacity) + v_opacity) / 2.0, 
+                    tile_width, 
+                    tile_height
+                ));
             }
         }
     }
@@ -247,7 +454,7 @@ impl Level {
 
         btn_text_sizes = game.get_font().measure_text(
             game.get_locale().get(self.btn_game_exit.get_title()
-        ).unwrap(), BTN_TEXT_FONTSIZE, game.get_font_spacing());
+        ), BTN_TEXT_FONTSIZE, game.get_font_spacing());
         self.btn_game_exit.set_rec(Rectangle {
             x: window_width - 10.0 - btn_text_sizes.x - default_btn_width_padding, 
             y: 80.0, 
@@ -257,7 +464,7 @@ impl Level {
 
         btn_text_sizes = game.get_font().measure_text(
             game.get_locale().get(self.btn_after_game_try_again.get_title()
-        ).unwrap(), BTN_TEXT_FONTSIZE, game.get_font_spacing());
+        ), BTN_TEXT_FONTSIZE, game.get_font_spacing());
         self.btn_after_game_try_again.set_rec(Rectangle {
             x: window_width / 2.0 - btn_text_sizes.x - default_btn_width_padding, 
             y: window_height - 100.0, 
@@ -267,7 +474,7 @@ impl Level {
 
         btn_text_sizes = game.get_font().measure_text(
             game.get_locale().get(self.btn_after_game_exit.get_title()
-        ).unwrap(), BTN_TEXT_FONTSIZE, game.get_font_spacing());
+        ), BTN_TEXT_FONTSIZE, game.get_font_spacing());
         self.btn_after_game_exit.set_rec(Rectangle {
             x: window_width / 2.0 + 50.0, 
             y: window_height - 100.0, 
@@ -281,6 +488,8 @@ impl Level {
             self.draw_game(d, &game, None);
             self.draw_timer(d, &game);
             self.draw_score(d, &game);
+            self.draw_next_number(d, &game);
+            self.draw_typed_number(d, &game);
             self.draw_game_exit_button(d, &game);
         } else if game.get_state() == game::GameState::Win {
             self.draw_game(d, &game, Some(0.3));
@@ -293,41 +502,50 @@ impl Level {
 
     fn draw_game(&self, d: &mut RaylibDrawHandle, game: &game::Game, color_opacity: Option<f32>) {
         let mut text: String;
-        let mut index: i32;
         let mut text_sizes: Vector2;
         let mut text_padding: Vector2;
 
         let color_opacity: f32 = if color_opacity == None { 1.0 } else { color_opacity.unwrap() };
-        let mut text_color: Color = Color::BLACK;
-        let is_hard_difficulty: bool = game.get_difficulty() == game::GameDifficulty::Hard;
+        let mut text_color: Color;
+        let is_hard_difficulty: bool = self.difficulty == game::GameDifficulty::Hard;
 
         for (i, el) in self.buttons.iter().enumerate() {
-            index = i as i32;
-            text = format!("{0}", self.numbers[i]);
-            text_sizes = game.get_font().measure_text(&text, 48.0, game.get_font_spacing());
+            text_color = Self::get_tile_text_color(self.board.get_tile(i).color);
+            text = format!("{0}", self.board.get_number(i));
+            text_sizes = game.get_font().measure_text(&text, self.tile_font_size, game.get_font_spacing());
             text_padding = Vector2 {
-                x: el.x + (RECTANGLE_WIDTH - text_sizes.x) / 2.0, 
-                y: el.y + (RECTANGLE_HEIGHT - text_sizes.y) / 2.0
+                x: el.x + (el.width - text_sizes.x) / 2.0, 
+                y: el.y + (el.height - text_sizes.y) / 2.0
             };
 
-            if self.correct_buttons.contains(&index) {
+            if self.board.is_cleared(i) {
                 if is_hard_difficulty {
                     d.draw_rectangle_lines_ex(el, 2.0, Color::BLACK.alpha(color_opacity));
                 } else {
                     d.draw_rectangle_rec(el, Color::GREEN.alpha(color_opacity));
                     text_color = Color::WHITE;
                 }
-            } else if self.incorrect_btn_index == index {
+            } else if self.board.get_incorrect_tile() == Some(i) {
                 d.draw_rectangle_rec(el, Color::RED.alpha(color_opacity));
                 text_color = Color::WHITE;
-            } else if self.active_btn_index == index {
+            } else if self.active_btn_index == i as i32 {
                 d.draw_rectangle_rec(el, Color::LIGHTGREEN.alpha(color_opacity));
             } else {
                 d.draw_rectangle_lines_ex(el, 2.0, Color::BLACK.alpha(color_opacity));
             }
-            d.draw_text_ex(game.get_font(), &text, text_padding, 48.0, game.get_font_spacing(), text_color.alpha(color_opacity));
-            text_color = Color::BLACK;
-        }        
+            d.draw_text_ex(game.get_font(), &text, text_padding, self.tile_font_size, game.get_font_spacing(), text_color.alpha(color_opacity));
+        }
+
+        if let Some(index) = self.cursor_index {
+            d.draw_rectangle_lines_ex(self.buttons[index], FOCUS_FRAME_WIDTH, FOCUS_COLOR.alpha(color_opacity));
+        }
+    }
+
+    fn get_tile_text_color(color: TileColor) -> Color {
+        match color {
+            TileColor::Black => Color::BLACK,
+            TileColor::Red => Color::RED,
+        }
     }
 
     fn draw_timer(&self, d: &mut RaylibDrawHandle, game: &game::Game) {
@@ -335,14 +553,32 @@ impl Level {
     }
 
     fn draw_score(&self, d: &mut RaylibDrawHandle, game: &game::Game) {
-        let text: String = format!("{} {} {}", 
-                                   game.get_locale().get(consts::SCORE_RESULT_1_STRING_NAME).unwrap(), self.score, 
-                                   game.get_locale().get(consts::SCORE_RESULT_2_STRING_NAME).unwrap());
+        let text: String = game.get_locale().format(consts::SCORE_RESULT_STRING_NAME, &[
+            ("points", &self.scoring.get_breakdown().get_total().to_string()),
+        ]);
         draw_text_center(d, text.as_str(), 24.0, 36.0, Color::GREEN, &game);
     }
 
+    fn draw_next_number(&self, d: &mut RaylibDrawHandle, game: &game::Game) {
+        if let Some(tile) = self.board.next_expected() {
+            let text: String = game.get_locale().format(consts::NEXT_NUMBER_STRING_NAME, &[("number", &tile.number.to_string())]);
+            let text_color: Color = match tile.color {
+                TileColor::Black => Color::DARKGRAY,
+                TileColor::Red => Color::RED,
+            };
+            draw_text_center(d, text.as_str(), 70.0, 36.0, text_color, &game);
+        }
+    }
+
+    fn draw_typed_number(&self, d: &mut RaylibDrawHandle, game: &game::Game) {
+        if !self.typed_number.is_empty() {
+            let text: String = game.get_locale().format(consts::TYPED_NUMBER_STRING_NAME, &[("number", &self.typed_number)]);
+            draw_text_center(d, text.as_str(), game.get_window_height() - 60.0, 36.0, FOCUS_COLOR, &game);
+        }
+    }
+
     fn draw_game_exit_button(&self, d: &mut RaylibDrawHandle, game: &game::Game) {
-        let btn_text_sizes: Vector2 = game.get_font().measure_text(game.get_locale().get(consts::EXIT_BTN_STRING_NAME).unwrap(), BTN_TEXT_FONTSIZE, game.get_font_spacing());
+        let btn_text_sizes: Vector2 = game.get_font().measure_text(game.get_locale().get(consts::EXIT_BTN_STRING_NAME), BTN_TEXT_FONTSIZE, game.get_font_spacing());
         let btn_padding: Vector2 = Vector2 {
             x: self.btn_game_exit.get_rec().x + (self.btn_game_exit.get_rec().width - btn_text_sizes.x) / 2.0, 
             y: self.btn_game_exit.get_rec().y + (self.btn_game_exit.get_rec().height - btn_text_sizes.y) / 2.0
@@ -353,29 +589,92 @@ impl Level {
         } else {
             d.draw_rectangle_rec(self.btn_game_exit.get_rec(), self.btn_game_exit.get_color());
         }
-        d.draw_text_ex(game.get_font(), game.get_locale().get(consts::EXIT_BTN_STRING_NAME).unwrap(), btn_padding, BTN_TEXT_FONTSIZE, game.get_font_spacing(), Color::BLACK);
+        d.draw_text_ex(game.get_font(), game.get_locale().get(consts::EXIT_BTN_STRING_NAME), btn_padding, BTN_TEXT_FONTSIZE, game.get_font_spacing(), Color::BLACK);
     }
 
     fn draw_win(&self, d: &mut RaylibDrawHandle, game: &game::Game) {
-        draw_text_center(d, game.get_locale().get(consts::WIN_RESULT_STRING_NAME).unwrap(), 30.0, 60.0, Color::GREEN, &game);
+        draw_text_center(d, game.get_locale().get(consts::WIN_RESULT_STRING_NAME), 30.0, 60.0, Color::GREEN, &game);
+        self.draw_seed(d, game);
+
+        // Completion time is the main result of time attack, the score is shown below it
+        if self.play_mode == game::PlayMode::TimeAttack {
+            let time_text: String = game.get_locale().format(consts::TIME_RESULT_STRING_NAME, &[
+                ("time", &timer::format_precise_time(self.timer.get_elapsed_time())),
+            ]);
+            draw_text_center(d, time_text.as_str(), 170.0, 60.0, Color::BLACK, &game);
+            self.draw_score_breakdown(d, game, 260.0);
+        } else {
+            self.draw_score_breakdown(d, game, 170.0);
+        }
+        self.draw_record_place(d, game);
 
         self.draw_after_game_buttons(d, game);
     }
 
     fn draw_lose(&self, d: &mut RaylibDrawHandle, game: &game::Game) {
-        let lose_text: String = format!("{} {} {} {} {}", 
-                                        game.get_locale().get(consts::LOSE_RESULT_1_STRING_NAME).unwrap(), self.score, 
-                                        game.get_locale().get(consts::LOSE_RESULT_2_STRING_NAME).unwrap(), self.fails, 
-                                        game.get_locale().get(consts::LOSE_RESULT_3_STRING_NAME).unwrap());
+        let breakdown: ScoreBreakdown = self.scoring.get_breakdown();
+        let lose_text: String = game.get_locale().format(consts::LOSE_RESULT_STRING_NAME, &[
+            ("points", &breakdown.get_total().to_string()),
+            ("fails", &breakdown.fails.to_string()),
+        ]);
         draw_text_center(d, lose_text.as_str(), 30.0, 60.0, Color::RED, &game);
+        self.draw_seed(d, game);
+        self.draw_score_breakdown(d, game, 170.0);
+        self.draw_record_place(d, game);
 
         self.draw_after_game_buttons(d, game);
     }
 
+    fn draw_seed(&self, d: &mut RaylibDrawHandle, game: &game::Game) {
+        let text: String = match self.daily_run {
+            Some(run) if run.is_practice => format!("{} {}", 
+                                                    game.get_locale().format(consts::DAILY_RESULT_STRING_NAME, &[("date", &run.date.to_string())]), 
+                                                    game.get_locale().get(consts::DAILY_PRACTICE_STRING_NAME)),
+            Some(run) => game.get_locale().format(consts::DAILY_RESULT_STRING_NAME, &[("date", &run.date.to_string())]),
+            None => game.get_locale().format(consts::SEED_RESULT_STRING_NAME, &[("seed", &self.board.get_seed().to_string())]),
+        };
+        draw_text_center(d, text.as_str(), 110.0, 36.0, Color::DARKGRAY, &game);
+    }
+
+    fn draw_score_breakdown(&self, d: &mut RaylibDrawHandle, game: &game::Game, y: f32) {
+        let breakdown: ScoreBreakdown = self.scoring.get_breakdown();
+        let rows: [(&str, i32, i32, i32); 4] = [
+            (consts::SCORE_CLICKS_STRING_NAME, breakdown.correct_clicks, 0, breakdown.base_points),
+            (consts::SCORE_STREAK_STRING_NAME, 0, breakdown.best_streak, breakdown.streak_bonus),
+            (consts::SCORE_SPEED_STRING_NAME, 0, 0, breakdown.speed_bonus),
+            (consts::SCORE_FAILS_STRING_NAME, breakdown.fails, 0, breakdown.fail_penalty),
+        ];
+
+        for (i, (title, count, streak, points)) in rows.iter().enumerate() {
+            let text: String = game.get_locale().format(title, &[
+                ("count", &count.to_string()),
+                ("streak", &streak.to_string()),
+                ("points", &points.to_string()),
+            ]);
+            draw_text_center(d, text.as_str(), y + 44.0 * i as f32, 36.0, Color::DARKGRAY, &game);
+        }
+
+        let total_text: String = game.get_locale().format(consts::SCORE_TOTAL_STRING_NAME, &[("points", &breakdown.get_total().to_string())]);
+        draw_text_center(d, total_text.as_str(), y + 44.0 * rows.len() as f32 + 16.0, 48.0, Color::BLACK, &game);
+    }
+
+    fn draw_record_place(&self, d: &mut RaylibDrawHandle, game: &game::Game) {
+        let text: String = match self.record_place {
+            Some(0) => game.get_locale().get(consts::NEW_RECORD_STRING_NAME).to_string(),
+            Some(place) => game.get_locale().format(consts::RECORD_PLACE_STRING_NAME, &[("place", &(place + 1).to_string())]),
+            None => return,
+        };
+        draw_text_center(d, text.as_str(), game.get_window_height() - 180.0, 48.0, Color::ORANGE, &game);
+    }
+
     fn draw_after_game_buttons(&self, d: &mut RaylibDrawHandle, game: &game::Game) {
         for btn in [&self.btn_after_game_try_again, &self.btn_after_game_exit].iter() {
+            if !self.can_try_again() && btn.get_title() == consts::TRY_AGAIN_BTN_STRING_NAME {
+                continue;
+            }
+
             // Draw button
-            let btn_text_sizes: Vector2 = game.get_font().measure_text(game.get_locale().get(btn.get_title()).unwrap(), BTN_TEXT_FONTSIZE, game.get_font_spacing());
+            let btn_text_sizes: Vector2 = game.get_font().measure_text(game.get_locale().get(btn.get_title()), BTN_TEXT_FONTSIZE, game.get_font_spacing());
             let btn_padding: Vector2 = Vector2 {
                 x: btn.get_rec().x + (btn.get_rec().width - btn_text_sizes.x) / 2.0, 
                 y: btn.get_rec().y + (btn.get_rec().height - btn_text_sizes.y) / 2.0
@@ -386,7 +685,7 @@ impl Level {
             } else {
                 d.draw_rectangle_rec(btn.get_rec(), btn.get_color());
             }
-            d.draw_text_ex(game.get_font(), game.get_locale().get(btn.get_title()).unwrap(), btn_padding, BTN_TEXT_FONTSIZE, game.get_font_spacing(), Color::BLACK);
+            d.draw_text_ex(game.get_font(), game.get_locale().get(btn.get_title()), btn_padding, BTN_TEXT_FONTSIZE, game.get_font_spacing(), Color::BLACK);
         }
     }
 }
\ No newline at end of file
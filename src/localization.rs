@@ -1,59 +1,173 @@
 use raylib::prelude::*;
 use raylib::core::texture::Texture2D;
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::BufReader;
-use quick_xml::events::{BytesStart, Event};
-use quick_xml::Reader;
+use std::path::{ Path, PathBuf };
+use std::time::SystemTime;
+
+use crate::assets::{ self, AssetError };
+use crate::consts;
+use crate::paths;
+use crate::translations::{ self, LocalePack, Translations, BASE_LOCALE_CODE };
 
 pub struct Locale {
     code: String,
     language: String,
+    font_path: Option<String>,
+    flag_path: String,
+    is_bundled: bool,
+    pack_dirs: Vec<PathBuf>,
     texture: Option<Texture2D>,
-    translations: HashMap<String, String>,
+    translations: Translations,
+    missing_keys: Vec<String>,
 }
 
 impl Locale {
-    pub fn load(path: &str) -> Result<Vec<Self>, String> {
-        let mut reader: Reader<BufReader<File>> = Reader::from_file(path).unwrap();
-        reader.config_mut().trim_text(true);
-
-        let mut locales: Vec<Locale> = Vec::new();
-        let mut buf: Vec<u8> = Vec::new();
-
-        loop {
-            match reader.read_event_into(&mut buf) {
-                Ok(Event::Start(e)) => {
-                    match e.name().as_ref() {
-                        b"language" => {
-                            locales.push(Self { 
-                                code: Self::read_attribute(&e, b"code")?, 
-                                language: Self::read_text(&mut reader)?,
-                                texture: None,
-                                translations: HashMap::new(), 
-                            });
-                        },
-                        _ => (),
-                    }
-                }
-                Ok(Event::Eof) => break,
-                Err(e) => return Err(format!("Error parsing XML at position {}: {:?}", reader.error_position(), e)),
-                _ => (),
+    pub fn load(path: &str) -> Result<Vec<Self>, AssetError> {
+        let data: String = assets::read_to_string(path)?;
+        let mut locales: Vec<Locale> = translations::parse_language_list(&data)
+            .map_err(|e| AssetError::invalid(path, e.to_string()))?
+            .into_iter()
+            .map(|entry| Self::new(entry.code, entry.language, entry.font, true))
+            .collect();
+
+        // Broken locale is left out, the game can still be played in other languages
+        locales.retain_mut(|locale| match locale.load_from_xml() {
+            Ok(_) => true,
+            Err(e) => {
+                eprintln!("{}", e);
+                false
+            },
+        });
+
+        // Packs installed by the user add new languages or override strings of the bundled ones
+        for dir in paths::get_locale_pack_dirs() {
+            if let Err(e) = Self::load_pack(&mut locales, &dir) {
+                eprintln!("{}", e);
             }
-            buf.clear();
         }
 
-        for locale in locales.iter_mut() {
-            locale.load_from_xml().expect("Error parsing locale XML");
+        if locales.is_empty() {
+            return Err(AssetError::invalid(path, "None of the listed locales could be loaded".to_string()));
         }
 
+        Self::apply_fallback(&mut locales);
+
         Ok(locales)
     }
 
-    pub fn get(&self, key: &str) -> Option<&String> {
+    fn new(code: String, language: String, font_path: Option<String>, is_bundled: bool) -> Self {
+        Self {
+            flag_path: format!("images/locales/{}.png", code),
+            code: code,
+            language: language,
+            font_path: font_path,
+            is_bundled: is_bundled,
+            pack_dirs: Vec::new(),
+            texture: None,
+            translations: Translations::default(),
+            missing_keys: Vec::new(),
+        }
+    }
+
+    fn load_pack(locales: &mut Vec<Locale>, dir: &Path) -> Result<(), AssetError> {
+        let pack: LocalePack = Self::read_pack(dir)?;
+        let index: usize = match locales.iter().position(|x| x.code == pack.code) {
+            Some(index) => index,
+            None => {
+                let language: String = pack.language.clone().ok_or_else(|| AssetError::invalid(
+                    &dir.join(paths::LOCALE_PACK_FILE).display().to_string(),
+                    "Attribute \"language\" is required for a new locale".to_string(),
+                ))?;
+                locales.push(Self::new(pack.code.clone(), language, None, false));
+                locales.len() - 1
+            },
+        };
+
+        locales[index].pack_dirs.push(dir.to_path_buf());
+        locales[index].apply_pack(dir, pack);
+
+        Ok(())
+    }
+
+    fn read_pack(dir: &Path) -> Result<LocalePack, AssetError> {
+        // Files of a pack are given by absolute paths, which the assets module reads as they are
+        let file: String = dir.join(paths::LOCALE_PACK_FILE).display().to_string();
+        translations::parse_locale_pack(&assets::read_to_string(&file)?).map_err(|e| AssetError::invalid(&file, e.to_string()))
+    }
+
+    fn apply_pack(&mut self, dir: &Path, pack: LocalePack) {
+        if let Some(language) = pack.language {
+            self.language = language;
+        }
+        if let Some(font) = pack.font {
+            self.font_path = Some(dir.join(font).display().to_string());
+        }
+        let flag_path: PathBuf = dir.join(paths::LOCALE_PACK_FLAG_FILE);
+        if flag_path.is_file() {
+            self.flag_path = flag_path.display().to_string();
+        }
+        for (key, value) in pack.strings {
+            self.translations.insert(key, value);
+        }
+    }
+
+    // Latest modification time of the files the strings come from, embedded files have none
+    pub fn get_modified_time(&self) -> Option<SystemTime> {
+        let mut files: Vec<PathBuf> = self.pack_dirs.iter().map(|x| x.join(paths::LOCALE_PACK_FILE)).collect();
+        if self.is_bundled {
+            files.push(paths::get_asset_path(&format!("locales/{}.xml", self.code)));
+        }
+        files.iter().filter_map(|x| std::fs::metadata(x).and_then(|x| x.modified()).ok()).max()
+    }
+
+    // Fallback of all locales has to be applied again after any of them is reloaded
+    pub fn reload(&mut self) -> Result<(), AssetError> {
+        // Broken file keeps the strings loaded before, so a typo doesn't wipe out the whole language
+        let previous: Translations = std::mem::take(&mut self.translations);
+        let result: Result<(), AssetError> = self.load_strings();
+        if result.is_err() {
+            self.translations = previous;
+        }
+        result
+    }
+
+    fn load_strings(&mut self) -> Result<(), AssetError> {
+        if self.is_bundled {
+            self.load_from_xml()?;
+        }
+        for dir in self.pack_dirs.clone() {
+            let pack: LocalePack = Self::read_pack(&dir)?;
+            self.apply_pack(&dir, pack);
+        }
+        Ok(())
+    }
+
+    pub fn apply_fallback(locales: &mut [Locale]) {
+        let base: Translations = match locales.iter().find(|x| x.code == BASE_LOCALE_CODE) {
+            Some(locale) => locale.translations.clone(),
+            None => Translations::default(),
+        };
+
+        for locale in locales.iter_mut() {
+            locale.translations.set_fallback(&base);
+            locale.missing_keys = locale.translations.get_missing_keys(&consts::ALL_STRING_NAMES);
+            if !locale.missing_keys.is_empty() {
+                eprintln!("Locale \"{}\" is missing {} keys: {}", locale.code, locale.missing_keys.len(), locale.missing_keys.join(", "));
+            }
+        }
+    }
+
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
         self.translations.get(key)
     }
 
+    pub fn format(&self, key: &str, args: &[(&str, &str)]) -> String {
+        translations::format_message(self.get(key), &self.code, args)
+    }
+
+    pub fn get_missing_keys(&self) -> &Vec<String> {
+        &self.missing_keys
+    }
+
     pub fn get_code(&self) -> &String {
         &self.code
     }
@@ -62,14 +176,26 @@ impl Locale {
         &self.language
     }
 
+    pub fn get_font_path(&self) -> &Option<String> {
+        &self.font_path
+    }
+
+    pub fn get_texts(&self) -> impl Iterator<Item = &str> {
+        self.translations.get_texts().chain([self.language.as_str()])
+    }
+
+    pub fn get_glyphs(&self) -> String {
+        translations::collect_glyphs(self.get_texts())
+    }
+
     pub fn get_texture(&self) -> &Option<Texture2D> {
         &self.texture
     }
 
-    pub fn load_texture(&mut self, rl: &mut RaylibHandle, thread: &RaylibThread) {
-        let filename: String = format!("assets/images/locales/{}.png", self.get_code());
-        let img: Image = Image::load_image(&filename).unwrap();
-        self.texture = Some(rl.load_texture_from_image(thread, &img).unwrap());
+    pub fn load_texture(&mut self, rl: &mut RaylibHandle, thread: &RaylibThread) -> Result<(), AssetError> {
+        let img: Image = assets::load_image(&self.flag_path)?;
+        self.texture = Some(rl.load_texture_from_image(thread, &img).map_err(|e| AssetError::invalid(&self.flag_path, e.to_string()))?);
+        Ok(())
     }
 
     pub fn unload_texture(&mut self, rl: &mut RaylibHandle, thread: &RaylibThread) {
@@ -82,52 +208,14 @@ impl Locale {
         }
     }
 
-    fn load_from_xml(&mut self) -> Result<bool, String> {
-        let path: String = format!("assets/locales/{}.xml", self.code);
-        let mut reader: Reader<BufReader<File>> = Reader::from_file(&path).unwrap();
-        reader.config_mut().trim_text(true);
-
-        let mut buf: Vec<u8> = Vec::new();
-
-        loop {
-            match reader.read_event_into(&mut buf) {
-                Ok(Event::Start(e)) => {
-                    match e.name().as_ref() {
-                        b"string" => {
-                            let key = Self::read_attribute(&e, b"name")?;
-                            let value = Self::read_text(&mut reader)?;
-                            self.translations.insert(key, value);
-                        },
-                        _ => (),
-                    }
-                }
-                Ok(Event::Eof) => break,
-                Err(e) => return Err(format!("Error parsing XML at position {}: {:?}", reader.error_position(), e)),
-                _ => (),
-            }
-            buf.clear();
-        }
-
-        Ok(true)
-    }
+    fn load_from_xml(&mut self) -> Result<bool, AssetError> {
+        let path: String = format!("locales/{}.xml", self.code);
+        let data: String = assets::read_to_string(&path)?;
 
-    fn read_text(reader: &mut Reader<BufReader<File>>) -> Result<String, String> {
-        let mut buf = Vec::new();
-        match reader.read_event_into(&mut buf) {
-            Ok(Event::Text(e)) => Ok(e.unescape().unwrap().into_owned()),
-            _ => Err("Expected text".to_string()),
+        for (key, value) in translations::parse_strings(&data).map_err(|e| AssetError::invalid(&path, e.to_string()))? {
+            self.translations.insert(key, value);
         }
-    }
-    
-    fn read_attribute(e: &BytesStart, name: &[u8]) -> Result<String, String> {
-        let attr_value_result = e.attributes()
-            .filter(|a| a.as_ref().unwrap().key.0 == name)
-            .map(|a| a.unwrap().unescape_value().map_err(|e| e.to_string()))
-            .last().ok_or_else(|| "Attribute not found".to_string());
 
-        match attr_value_result {
-            Ok(x) => Ok(x?.into_owned()),
-            Err(x) => Err(x),
-        }
+        Ok(true)
     }
 }
\ No newline at end of file
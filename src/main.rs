@@ -1,45 +1,104 @@
 #![windows_subsystem = "windows"]
 
 use raylib::prelude::*;
-use raylib::core::texture::Image;
 use std::env;
 
+mod assets;
+mod board;
+mod cli;
+mod console;
 mod consts;
+mod daily;
+mod debug;
+mod error_screen;
 mod game;
+mod input;
 mod level;
 mod localization;
 mod menu;
+mod paths;
+mod records;
+mod rng;
+mod scoring;
+mod sequence;
+mod settings;
 mod timer;
+mod translations;
 mod utils;
+mod validate;
 
+use assets::AssetError;
+use cli::{ Command, LaunchOptions };
+use debug::DebugOverlay;
 use game::Game;
+use input::Input;
 use menu::Menu;
 use level::Level;
+use localization::Locale;
 
 fn main() {
+    let options: LaunchOptions = match cli::parse(&env::args().skip(1).collect::<Vec<String>>()) {
+        Ok(Command::Play(options)) => options,
+        Ok(Command::Help) => {
+            attach_console();
+            println!("{}", cli::USAGE);
+            return;
+        },
+        // Translators check locale files without opening the game window
+        Ok(Command::ValidateLocales) => {
+            attach_console();
+            std::process::exit(validate::run());
+        },
+        Err(e) => exit_with_usage(&e),
+    };
+    // Locales are loaded once before the window, broken locale list is shown by the error screen later
+    let all_locales: Result<Vec<Locale>, AssetError> = Locale::load("locales/codes.xml");
+    if let Ok(locales) = &all_locales {
+        let locale_codes: Vec<String> = locales.iter().map(|x| x.get_code().clone()).collect();
+        if let Err(e) = cli::check_locale(&options, &locale_codes) {
+            exit_with_usage(&e);
+        }
+    }
+
     // Init window
+    let (window_width, window_height) = options.window_size.unwrap_or((Game::DEFAULT_WINDOW_WIDTH, Game::DEFAULT_WINDOW_HEIGHT));
     let (mut rl, thread) = raylib::init()
-        .size(Game::DEFAULT_WINDOW_WIDTH, Game::DEFAULT_WINDOW_HEIGHT)
+        .size(window_width, window_height)
         .vsync()
         .build();
 
-    let mut game: Game = Game::new(&mut rl, &thread, game::GameMode::Release);
+    let mut game: Game = match all_locales.and_then(|x| Game::new(&mut rl, &thread, x, options.mode, options.config_path, options.overrides)) {
+        Ok(game) => game,
+        Err(e) => {
+            error_screen::show(&mut rl, &thread, &e);
+            return;
+        },
+    };
+    game.set_session_seed(options.seed);
 
     if env::consts::OS != "macos" {
         // ToDo: in MacOS the app should be properly bundled to show icon
-        rl.set_window_icon(Image::load_image("assets/images/icon.png").unwrap());
+        match assets::load_image("images/icon.png") {
+            Ok(icon) => rl.set_window_icon(icon),
+            Err(e) => eprintln!("{}", e),
+        }
     }
 
     update_window_sizes(&mut rl, &mut game);
 
     let mut menu: Menu = Menu::new(&game);
     let mut level: Level = Level::new(&game);
+    let mut debug_overlay: DebugOverlay = DebugOverlay::default();
+    let mut input: Input = Input::default();
 
     while !rl.window_should_close() {
-        // Processing controllers
-        game.process_controller(&mut rl, &mut menu, &mut level);
-        menu.process_controller(&mut rl, &thread, &mut game, &mut level);
-        level.process_controller(&rl, &mut game);
+        // Processing controllers, the debug console takes the keyboard while it's open
+        input.update(&mut rl);
+        if !debug_overlay.process_controller(&mut rl, &mut game, &mut level) {
+            game.process_controller(&mut rl, &input, &mut menu, &mut level);
+            menu.process_controller(&mut rl, &thread, &input, &mut game, &mut level);
+            level.process_controller(&mut rl, &input, &mut game);
+        }
 
         // Drawing
         let mut d = rl.begin_drawing(&thread);
@@ -48,11 +107,43 @@ fn main() {
         game.draw(&mut d);
         menu.draw(&mut d, &game);
         level.draw(&mut d, &game);
+        debug_overlay.draw(&mut d, &game, &menu, &level);
+    }
+}
+
+// The game is built without a console window on Windows, so commands print into the console they were started from
+#[cfg(windows)]
+fn attach_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+
+    // Started from Explorer there is no console to attach to, and the output is lost as before
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
     }
 }
 
+#[cfg(not(windows))]
+fn attach_console() {}
+
+fn exit_with_usage(error: &str) -> ! {
+    attach_console();
+    eprintln!("{}\n\n{}", error, cli::USAGE);
+    std::process::exit(2);
+}
+
 fn update_window_sizes(rl: &mut RaylibHandle, game: &mut Game) {
     let monitor_index: i32 = get_current_monitor_index();
+
+    // Window stays in the default size when fullscreen mode was turned off in settings
+    if !game.get_settings().is_fullscreen {
+        game.set_fullscreen_sizes(get_monitor_width(monitor_index), get_monitor_height(monitor_index));
+        game.set_window_sizes(rl.get_screen_width(), rl.get_screen_height());
+        return;
+    }
     
     // ToDo: need to check if it requires on Windows and Linux
     rl.toggle_borderless_windowed();
@@ -1,15 +1,26 @@
 use raylib::prelude::*;
 use raylib::consts::MouseButton::*;
+use raylib::consts::KeyboardKey::*;
 
+use crate::board::Tile;
 use crate::consts;
+use crate::daily::{ self, DailyAttempt };
 use crate::game;
+use crate::input::{ Action, Input };
 use crate::level;
-use crate::utils::draw_text_center;
+use crate::records::{ Record, RecordKey };
+use crate::sequence::SequenceRule;
+use crate::timer::format_precise_time;
+use crate::utils::{ draw_text_center, FOCUS_COLOR, FOCUS_FRAME_WIDTH };
 
 const DEFAULT_MENU_ITEM_WIDTH: f32 = 400.0;
 const DEFAULT_MENU_ITEM_HEIGHT: f32 = 80.0;
 const DEFAULT_MENU_ITEMS_DIFF: f32 = DEFAULT_MENU_ITEM_HEIGHT / 2.0;
+const MIN_MENU_ITEMS_DIFF: f32 = 10.0;
 const DEFAULT_MENU_ITEM_FONT_SIZE: f32 = 54.0;
+// Size of the language button, when its flag image is missing
+const DEFAULT_FLAG_WIDTH: i32 = 200;
+const DEFAULT_FLAG_HEIGHT: i32 = 120;
 
 struct HelpTextRow {
     content: &'static str,
@@ -17,9 +28,10 @@ struct HelpTextRow {
     padding_bottom: u16,
 }
 
-const HELP_TEXT_ROWS: [HelpTextRow; 10] = [
+const HELP_TEXT_ROWS: [HelpTextRow; 11] = [
     HelpTextRow { font_size: 32, padding_bottom: 64, content: consts::HELP_TITLE_1_STRING_NAME, },
     HelpTextRow { font_size: 24, padding_bottom: 36, content: consts::HELP_TEXT_1_STRING_NAME, },
+    HelpTextRow { font_size: 24, padding_bottom: 36, content: consts::HELP_TEXT_SEQUENCE_STRING_NAME, },
     HelpTextRow { font_size: 24, padding_bottom: 36, content: consts::HELP_TEXT_2_STRING_NAME, },
     HelpTextRow { font_size: 24, padding_bottom: 50, content: consts::HELP_TEXT_3_1_STRING_NAME, },
     HelpTextRow { font_size: 24, padding_bottom: 64, content: consts::HELP_TEXT_3_2_STRING_NAME, },
@@ -34,13 +46,18 @@ const HELP_TEXT_ROWS: [HelpTextRow; 10] = [
 enum MenuAllItems {
     Start,
     Continue,
+    Daily,
+    Records,
     Settings,
     Help,
     Exit,
     Difficulty,
+    PlayMode,
     Language,
     Fullscreen,
     ToggleFPS,
+    BoardSize,
+    Sequence,
     Back,
 }
 
@@ -49,6 +66,8 @@ impl MenuAllItems {
         match *self {
             Self::Start => consts::START_MENU_STRING_NAME,
             Self::Continue => consts::CONTINUE_MENU_STRING_NAME,
+            Self::Daily => consts::DAILY_MENU_STRING_NAME,
+            Self::Records => consts::RECORDS_MENU_STRING_NAME,
             Self::Settings => consts::SETTINGS_MENU_STRING_NAME,
             Self::Help => consts::HELP_MENU_STRING_NAME,
             Self::Exit => consts::EXIT_MENU_STRING_NAME,
@@ -60,20 +79,24 @@ impl MenuAllItems {
     fn description(&self) -> &str {
         match *self {
             Self::Difficulty => consts::DIFFICULTY_MENU_STRING_NAME,
+            Self::PlayMode => consts::PLAY_MODE_MENU_STRING_NAME,
             Self::Language => consts::LANGUAGE_MENU_STRING_NAME,
             Self::Fullscreen => consts::FULLSCREEN_MENU_STRING_NAME,
             Self::ToggleFPS => consts::TOGGLE_FPS_MENU_STRING_NAME,
+            Self::BoardSize => consts::BOARD_SIZE_MENU_STRING_NAME,
+            Self::Sequence => consts::SEQUENCE_MENU_STRING_NAME,
             _ => "",
         }
     }
 }
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum MenuState {
     LanguageSelect,
     Primary,
     Settings,
     Help,
+    Records,
 }
 
 struct MenuItem {
@@ -92,41 +115,56 @@ pub struct Menu {
     items: Vec<MenuItem>,
     settings_items: Vec<MenuItem>,
     locale_items: Vec<LocaleItem>,
+    focus_index: Option<usize>,
 }
 
 impl Menu {
-    const PRIMARY_ITEMS: [MenuAllItems; 4] = [
+    const PRIMARY_ITEMS: [MenuAllItems; 6] = [
         MenuAllItems::Start,
+        MenuAllItems::Daily,
+        MenuAllItems::Records,
         MenuAllItems::Settings, 
         MenuAllItems::Help,
         MenuAllItems::Exit, 
     ];
-    const FULL_PRIMARY_ITEMS: [MenuAllItems; 5] = [
+    const FULL_PRIMARY_ITEMS: [MenuAllItems; 7] = [
         MenuAllItems::Start,
         MenuAllItems::Continue,
+        MenuAllItems::Daily,
+        MenuAllItems::Records,
         MenuAllItems::Settings, 
         MenuAllItems::Help,
         MenuAllItems::Exit, 
     ];
-    const SETTINGS_ITEMS: [MenuAllItems; 5] = [
+    const SETTINGS_ITEMS: [MenuAllItems; 8] = [
         MenuAllItems::Difficulty,
+        MenuAllItems::PlayMode,
+        MenuAllItems::BoardSize,
+        MenuAllItems::Sequence,
         MenuAllItems::Language,
         MenuAllItems::Fullscreen, 
         MenuAllItems::ToggleFPS, 
         MenuAllItems::Back,
     ];
 
+    fn get_items_diff(items_count: usize, window_height: f32) -> f32 {
+        // Gaps between items shrink when the whole list doesn't fit into the window
+        let free_space: f32 = window_height - DEFAULT_MENU_ITEM_HEIGHT - items_count as f32 * DEFAULT_MENU_ITEM_HEIGHT;
+        DEFAULT_MENU_ITEMS_DIFF.min(free_space / (items_count - 1) as f32).max(MIN_MENU_ITEMS_DIFF)
+    }
+
     fn construct_menu_items(menu_items: &[MenuAllItems], game: &game::Game) -> Vec<MenuItem> {
         let mut menu_item: MenuItem;
         let mut items: Vec<MenuItem> = Vec::with_capacity(menu_items.len());
-        let all_items_height: f32 = menu_items.len() as f32 * DEFAULT_MENU_ITEM_HEIGHT + (menu_items.len() - 1) as f32 * DEFAULT_MENU_ITEMS_DIFF;
+        let items_diff: f32 = Self::get_items_diff(menu_items.len(), game.get_window_height());
+        let all_items_height: f32 = menu_items.len() as f32 * DEFAULT_MENU_ITEM_HEIGHT + (menu_items.len() - 1) as f32 * items_diff;
         
         for (index, item) in menu_items.iter().enumerate() {
             if item.description() == "" {
                 menu_item = MenuItem {
                     btn: Rectangle {
                         x: (game.get_window_width() - DEFAULT_MENU_ITEM_WIDTH) / 2.0, 
-                        y: (game.get_window_height() - all_items_height) / 2.0 + index as f32 * (DEFAULT_MENU_ITEM_HEIGHT + DEFAULT_MENU_ITEMS_DIFF), 
+                        y: (game.get_window_height() - all_items_height) / 2.0 + index as f32 * (DEFAULT_MENU_ITEM_HEIGHT + items_diff), 
                         width: DEFAULT_MENU_ITEM_WIDTH, 
                         height: DEFAULT_MENU_ITEM_HEIGHT,
                     },
@@ -137,7 +175,7 @@ impl Menu {
                 menu_item = MenuItem {
                     btn: Rectangle {
                         x: game.get_window_width() / 8.0 * 7.0 - DEFAULT_MENU_ITEM_WIDTH, 
-                        y: (game.get_window_height() - all_items_height) / 2.0 + index as f32 * (DEFAULT_MENU_ITEM_HEIGHT + DEFAULT_MENU_ITEMS_DIFF), 
+                        y: (game.get_window_height() - all_items_height) / 2.0 + index as f32 * (DEFAULT_MENU_ITEM_HEIGHT + items_diff), 
                         width: DEFAULT_MENU_ITEM_WIDTH, 
                         height: DEFAULT_MENU_ITEM_HEIGHT,
                     },
@@ -156,17 +194,19 @@ impl Menu {
         let locales_len: usize = game.get_all_locales().len();
         
         if menu_state == MenuState::LanguageSelect {
-            let mut texture: &Texture2D;
             items = Vec::with_capacity(locales_len);
 
             for (index, locale) in game.get_all_locales().iter().enumerate() {
-                texture = locale.get_texture().as_ref().unwrap();
+                let (width, height): (i32, i32) = match locale.get_texture() {
+                    Some(texture) => (texture.width, texture.height),
+                    None => (DEFAULT_FLAG_WIDTH, DEFAULT_FLAG_HEIGHT),
+                };
                 items.push(LocaleItem {
                     btn: Rectangle {
-                        x: (game.get_window_width() - ((texture.width + 100) as f32 * locales_len as f32 - 100.0)) / 2.0 + (texture.width as f32 + 100.0) * index as f32, 
-                        y: (game.get_window_height() - texture.height as f32) / 2.0, 
-                        width: texture.width as f32, 
-                        height: texture.height as f32,
+                        x: (game.get_window_width() - ((width + 100) as f32 * locales_len as f32 - 100.0)) / 2.0 + (width as f32 + 100.0) * index as f32, 
+                        y: (game.get_window_height() - height as f32) / 2.0, 
+                        width: width as f32, 
+                        height: height as f32,
                     },
                     color: Color::WHITE,
                 });
@@ -179,16 +219,14 @@ impl Menu {
     }
 
     pub fn new(game: &game::Game) -> Self {
-        let menu_state: MenuState = match std::fs::File::open(game::Game::SAVE_CONFIG_PATH) {
-            Err(_) => MenuState::LanguageSelect,
-            Ok(_) => MenuState::Primary,
-        };
+        let menu_state: MenuState = if game.is_first_launch() { MenuState::LanguageSelect } else { MenuState::Primary };
 
         Self {
             state: menu_state,
             items: Self::construct_menu_items(&Self::PRIMARY_ITEMS, game),
             settings_items: Self::construct_menu_items(&Self::SETTINGS_ITEMS, game),
             locale_items: Self::construct_locale_items(menu_state, game),
+            focus_index: None,
         }
     }
 
@@ -198,6 +236,7 @@ impl Menu {
 
     pub fn set_state(&mut self, state: MenuState) {
         self.state = state;
+        self.focus_index = None;
     }
 
     pub fn update_btn_positions(&mut self, game: &game::Game) {
@@ -207,16 +246,19 @@ impl Menu {
         let items_length: f32 = self.items.len() as f32;
         let settings_items_length: f32 = self.settings_items.len() as f32;
 
-        let all_items_height: f32 = items_length * DEFAULT_MENU_ITEM_HEIGHT + (items_length - 1.0) * DEFAULT_MENU_ITEMS_DIFF;
-        let all_settings_items_height: f32 = settings_items_length * DEFAULT_MENU_ITEM_HEIGHT + (settings_items_length - 1.0) * DEFAULT_MENU_ITEMS_DIFF;
+        let items_diff: f32 = Self::get_items_diff(self.items.len(), window_height);
+        let settings_items_diff: f32 = Self::get_items_diff(self.settings_items.len(), window_height);
+
+        let all_items_height: f32 = items_length * DEFAULT_MENU_ITEM_HEIGHT + (items_length - 1.0) * items_diff;
+        let all_settings_items_height: f32 = settings_items_length * DEFAULT_MENU_ITEM_HEIGHT + (settings_items_length - 1.0) * settings_items_diff;
 
         for (index, item) in self.items.iter_mut().enumerate() {
             item.btn.x = (window_width - DEFAULT_MENU_ITEM_WIDTH) / 2.0;
-            item.btn.y = (window_height - all_items_height) / 2.0 + index as f32 * (DEFAULT_MENU_ITEM_HEIGHT + DEFAULT_MENU_ITEMS_DIFF);
+            item.btn.y = (window_height - all_items_height) / 2.0 + index as f32 * (DEFAULT_MENU_ITEM_HEIGHT + items_diff);
         }
 
         for (index, item) in self.settings_items.iter_mut().enumerate() {
-            item.btn.y = (window_height - all_settings_items_height) / 2.0 + index as f32 * (DEFAULT_MENU_ITEM_HEIGHT + DEFAULT_MENU_ITEMS_DIFF);
+            item.btn.y = (window_height - all_settings_items_height) / 2.0 + index as f32 * (DEFAULT_MENU_ITEM_HEIGHT + settings_items_diff);
 
             if item.title.value() == "" {
                 item.btn.x = window_width / 8.0 * 7.0 - DEFAULT_MENU_ITEM_WIDTH;
@@ -226,17 +268,18 @@ impl Menu {
         }
     }
 
-    pub fn process_controller(&mut self, rl: &mut RaylibHandle, thread: &RaylibThread, game: &mut game::Game, level: &mut level::Level) {
+    pub fn process_controller(&mut self, rl: &mut RaylibHandle, thread: &RaylibThread, input: &Input, game: &mut game::Game, level: &mut level::Level) {
         if game.get_state() != game::GameState::Menu { return; }
 
-        let mouse_pos: Vector2 = rl.get_mouse_position();
+        // Hover is shown only while the mouse is the last used device, otherwise the focus frame is
+        let mouse_pos: Option<Vector2> = if input.is_mouse_used() { Some(rl.get_mouse_position()) } else { None };
 
         if self.state == MenuState::Primary {
-            self.process_primary_menu_controller(rl, &mouse_pos, game, level);
+            self.process_primary_menu_controller(rl, input, mouse_pos, game, level);
         } else if self.state == MenuState::Settings {
-            self.process_settings_menu_controller(rl, &mouse_pos, game, level);
+            self.process_settings_menu_controller(rl, input, mouse_pos, game, level);
         } else if self.state == MenuState::LanguageSelect {
-            self.process_language_selector_controller(rl, thread, &mouse_pos, game, level);
+            self.process_language_selector_controller(rl, thread, input, mouse_pos, game, level);
         }
     }
 
@@ -244,129 +287,216 @@ impl Menu {
         if game.get_state() != game::GameState::Menu { return; }
 
         if self.state == MenuState::Primary {
-            for item in self.items.iter() {
-                self.draw_menu_button(d, game, item);
+            for (index, item) in self.items.iter().enumerate() {
+                self.draw_menu_button(d, game, item, self.focus_index == Some(index));
             }
         } else if self.state == MenuState::Settings {
-            for item in self.settings_items.iter() {
-                self.draw_menu_button(d, game, &item);
+            for (index, item) in self.settings_items.iter().enumerate() {
+                self.draw_menu_button(d, game, &item, self.focus_index == Some(index));
             }
         } else if self.state == MenuState::LanguageSelect {
             self.draw_language_selector(d, game);
         } else if self.state == MenuState::Help {
             self.draw_help_menu(d, game);
+        } else if self.state == MenuState::Records {
+            self.draw_records_menu(d, game);
         }
     }
 
-    fn process_primary_menu_controller(&mut self, rl: &mut RaylibHandle, mouse_pos: &Vector2, game: &mut game::Game, level: &mut level::Level) {
+    fn process_primary_menu_controller(&mut self, rl: &mut RaylibHandle, input: &Input, mouse_pos: Option<Vector2>, game: &mut game::Game, level: &mut level::Level) {
         let mut has_primary_menu_to_be_updated: bool = false;
         let items_count: usize = self.items.len();
 
-        if items_count == 5 && level.is_over() {
-            self.update_primary_menu(game, 4);
+        if items_count == Self::FULL_PRIMARY_ITEMS.len() && level.is_over() {
+            self.update_primary_menu(game, Self::PRIMARY_ITEMS.len());
         }
 
+        let mut activated_item: Option<MenuAllItems> = None;
         for item in self.items.iter_mut() {
-            if item.btn.check_collision_point_rec(mouse_pos) {
+            if mouse_pos.is_some_and(|x| item.btn.check_collision_point_rec(x)) {
                 item.color = Color::LIGHTGREEN;
                 if rl.is_mouse_button_released(MOUSE_BUTTON_LEFT) {
-                    match item.title {
-                        MenuAllItems::Start => {
-                            game.set_state(game::GameState::Game);
-                            level.start(game);
-                            has_primary_menu_to_be_updated = items_count == 4;
-                        },
-                        MenuAllItems::Continue => level.resume(game),
-                        MenuAllItems::Settings => self.state = MenuState::Settings,
-                        MenuAllItems::Help => self.state = MenuState::Help,
-                        MenuAllItems::Exit => std::process::exit(0),
-                        _ => {},
-                    }
+                    activated_item = Some(item.title);
                 }
             } else if item.color != Color::LIGHTGRAY {
                 item.color = Color::LIGHTGRAY;
             }
         }
+        if let Some(index) = self.process_focus_keys(rl, input, self.items.len(), false) {
+            activated_item = Some(self.items[index].title);
+        }
+
+        match activated_item {
+            Some(MenuAllItems::Start) => {
+                game.set_state(game::GameState::Game);
+                level.start(game);
+                has_primary_menu_to_be_updated = items_count == Self::PRIMARY_ITEMS.len();
+            },
+            Some(MenuAllItems::Daily) => {
+                game.set_state(game::GameState::Game);
+                level.start_daily(game);
+                has_primary_menu_to_be_updated = items_count == Self::PRIMARY_ITEMS.len();
+            },
+            Some(MenuAllItems::Continue) => level.resume(game),
+            Some(MenuAllItems::Records) => self.set_state(MenuState::Records),
+            Some(MenuAllItems::Settings) => self.set_state(MenuState::Settings),
+            Some(MenuAllItems::Help) => self.set_state(MenuState::Help),
+            Some(MenuAllItems::Exit) => std::process::exit(0),
+            _ => {},
+        }
 
         if has_primary_menu_to_be_updated {
-            self.update_primary_menu(game, 5);
+            self.update_primary_menu(game, Self::FULL_PRIMARY_ITEMS.len());
         }
     }
 
-    fn process_settings_menu_controller(&mut self, rl: &mut RaylibHandle, mouse_pos: &Vector2, game: &mut game::Game, level: &mut level::Level) {
-        let mut is_fullscreen_required: bool = false;
-
+    fn process_settings_menu_controller(&mut self, rl: &mut RaylibHandle, input: &Input, mouse_pos: Option<Vector2>, game: &mut game::Game, level: &mut level::Level) {
+        // Click and confirm change the value forward, left and right go in their direction
+        let mut activated_item: Option<(MenuAllItems, bool)> = None;
         for item in self.settings_items.iter_mut() {
-            if item.btn.check_collision_point_rec(mouse_pos) {
+            if mouse_pos.is_some_and(|x| item.btn.check_collision_point_rec(x)) {
                 item.color = Color::LIGHTGREEN;
                 if rl.is_mouse_button_released(MOUSE_BUTTON_LEFT) {
-                    match item.title {
-                        MenuAllItems::Difficulty => game.change_difficulty(game.get_difficulty()),
-                        MenuAllItems::Language => game.change_locale(level),
-                        MenuAllItems::Fullscreen => is_fullscreen_required = true,
-                        MenuAllItems::ToggleFPS => game.toggle_fps_monitor(),
-                        MenuAllItems::Back => self.state = MenuState::Primary,
-                        _ => {},
-                    }
+                    activated_item = Some((item.title, true));
                 }
             } else if item.color != Color::LIGHTGRAY {
                 item.color = Color::LIGHTGRAY;
             }
         }
-        if is_fullscreen_required {
-            game.toggle_fullscreen(rl, self, level);
+        if let Some(index) = self.process_focus_keys(rl, input, self.settings_items.len(), false) {
+            activated_item = Some((self.settings_items[index].title, true));
+        }
+        if let Some(index) = self.focus_index {
+            let title: MenuAllItems = self.settings_items[index].title;
+            if title != MenuAllItems::Back && (input.is_triggered(Action::Right) || input.is_triggered(Action::Left)) {
+                activated_item = Some((title, input.is_triggered(Action::Right)));
+            }
+        }
+
+        match activated_item {
+            Some((MenuAllItems::Difficulty, is_forward)) => game.change_difficulty(game.get_difficulty(), is_forward),
+            Some((MenuAllItems::PlayMode, _)) => game.change_play_mode(),
+            Some((MenuAllItems::BoardSize, is_forward)) => game.change_board_size(is_forward),
+            Some((MenuAllItems::Sequence, is_forward)) => game.change_sequence_rule(is_forward),
+            Some((MenuAllItems::Language, is_forward)) => game.change_locale(level, is_forward),
+            Some((MenuAllItems::Fullscreen, _)) => game.toggle_fullscreen(rl, self, level),
+            Some((MenuAllItems::ToggleFPS, _)) => game.toggle_fps_monitor(),
+            Some((MenuAllItems::Back, _)) => self.set_state(MenuState::Primary),
+            _ => {},
         }
     }
 
-    fn process_language_selector_controller(&mut self, rl: &mut RaylibHandle, thread: &RaylibThread, mouse_pos: &Vector2, game: &mut game::Game, level: &mut level::Level) {
+    fn process_language_selector_controller(&mut self, rl: &mut RaylibHandle, thread: &RaylibThread, input: &Input, mouse_pos: Option<Vector2>, game: &mut game::Game, level: &mut level::Level) {
+        let mut is_confirmed: bool = false;
         for (index, item) in self.locale_items.iter_mut().enumerate() {
-            if item.btn.check_collision_point_rec(mouse_pos) {
-                let locale_code: String = game.get_all_locales()[index].get_code().clone();
-                game.set_locale(&locale_code, level);
+            if mouse_pos.is_some_and(|x| item.btn.check_collision_point_rec(x)) {
+                if self.focus_index.is_none() {
+                    let locale_code: String = game.get_all_locales()[index].get_code().clone();
+                    game.set_locale(&locale_code, level);
+                }
                 item.color = Color::LIGHTGREEN;
                 if rl.is_mouse_button_released(MOUSE_BUTTON_LEFT) {
-                    game.update_config_file();
-
-                    for locale in game.get_all_locales_mut() {
-                        locale.unload_texture(rl, thread);
-                    }
-
-                    self.state = MenuState::Primary;
-                    self.locale_items = Vec::with_capacity(0);
+                    is_confirmed = true;
                     break;
-                    
                 }
             } else if item.color != Color::WHITE {
                 item.color = Color::WHITE;
             }
         }
+
+        // Focused language is shown right away, the same as the hovered one
+        let prev_focus_index: Option<usize> = self.focus_index;
+        if self.process_focus_keys(rl, input, self.locale_items.len(), true).is_some() {
+            is_confirmed = true;
+        }
+        if let Some(index) = self.focus_index {
+            if self.focus_index != prev_focus_index {
+                let locale_code: String = game.get_all_locales()[index].get_code().clone();
+                game.set_locale(&locale_code, level);
+            }
+        }
+
+        if is_confirmed {
+            game.update_config_file();
+
+            for locale in game.get_all_locales_mut() {
+                locale.unload_texture(rl, thread);
+            }
+
+            self.set_state(MenuState::Primary);
+            self.locale_items = Vec::with_capacity(0);
+        }
+    }
+
+    // Moves the focus of the keyboard or gamepad over the items of the current state, returns the index of the activated item
+    fn process_focus_keys(&mut self, rl: &RaylibHandle, input: &Input, items_count: usize, is_horizontal: bool) -> Option<usize> {
+        // Mouse takes over as soon as it's used, so hover and focus don't point at different items
+        if input.is_mouse_used() || self.focus_index.is_some_and(|x| x >= items_count) {
+            self.focus_index = None;
+        }
+        if items_count == 0 {
+            return None;
+        }
+
+        let (next_action, prev_action) = if is_horizontal { (Action::Right, Action::Left) } else { (Action::Down, Action::Up) };
+        let is_shift_down: bool = rl.is_key_down(KEY_LEFT_SHIFT) || rl.is_key_down(KEY_RIGHT_SHIFT);
+        let is_tab_pressed: bool = rl.is_key_pressed(KEY_TAB) || rl.is_key_pressed_repeat(KEY_TAB);
+
+        if input.is_triggered(next_action) || (is_tab_pressed && !is_shift_down) {
+            self.focus_index = Some(match self.focus_index {
+                Some(index) => (index + 1) % items_count,
+                None => 0,
+            });
+        } else if input.is_triggered(prev_action) || (is_tab_pressed && is_shift_down) {
+            self.focus_index = Some(match self.focus_index {
+                Some(index) => (index + items_count - 1) % items_count,
+                None => items_count - 1,
+            });
+        }
+
+        match self.focus_index {
+            Some(index) if input.is_triggered(Action::Confirm) => Some(index),
+            _ => None,
+        }
     }
 
-    fn draw_menu_button(&self, d: &mut RaylibDrawHandle, game: &game::Game, menu_item: &MenuItem) {
+    fn draw_menu_button(&self, d: &mut RaylibDrawHandle, game: &game::Game, menu_item: &MenuItem, is_focused: bool) {
         // Draw item button
-        let item_btn_title: &str = match &menu_item.title {
-            MenuAllItems::Difficulty => game.get_locale().get(game.get_difficulty().repr()).unwrap(),
-            MenuAllItems::Language => game.get_locale().get_language(),
+        let item_btn_title: String = match &menu_item.title {
+            MenuAllItems::Difficulty => game.get_locale().get(game.get_difficulty().repr()).to_string(),
+            MenuAllItems::PlayMode => game.get_locale().get(game.get_play_mode().repr()).to_string(),
+            MenuAllItems::Language => game.get_locale().get_language().clone(),
             MenuAllItems::Fullscreen => game.get_locale().get(
                 if game.get_settings().is_fullscreen {consts::DISABLE_BTN_STRING_NAME} else {consts::ENABLE_BTN_STRING_NAME}
-            ).unwrap(),
+            ).to_string(),
             MenuAllItems::ToggleFPS => game.get_locale().get(
                 if game.get_settings().is_fps_visible {consts::DISABLE_BTN_STRING_NAME} else {consts::ENABLE_BTN_STRING_NAME}
-            ).unwrap(),
-            _ => game.get_locale().get(menu_item.title.value()).unwrap(),
+            ).to_string(),
+            MenuAllItems::BoardSize => if game.is_board_size_auto() {
+                format!("{} ({})", game.get_locale().get(consts::AUTO_BOARD_SIZE_STRING_NAME), game.get_board_size())
+            } else {
+                game.get_board_size().to_string()
+            },
+            MenuAllItems::Sequence => Self::get_sequence_rule_title(game, game.get_sequence_rule()),
+            _ => game.get_locale().get(menu_item.title.value()).to_string(),
         };
         d.draw_rectangle_rec(menu_item.btn, menu_item.color);
-        let btn_text_sizes: Vector2 = game.get_font().measure_text(item_btn_title, DEFAULT_MENU_ITEM_FONT_SIZE, game.get_font_spacing());
+        if is_focused {
+            d.draw_rectangle_lines_ex(menu_item.btn, FOCUS_FRAME_WIDTH, FOCUS_COLOR);
+        }
+        let btn_text_sizes: Vector2 = game.get_font().measure_text(&item_btn_title, DEFAULT_MENU_ITEM_FONT_SIZE, game.get_font_spacing());
         let btn_padding: Vector2 = Vector2 {
             x: menu_item.btn.x + (menu_item.btn.width - btn_text_sizes.x) / 2.0, 
             y: menu_item.btn.y + (menu_item.btn.height - btn_text_sizes.y) / 2.0
         };
-        d.draw_text_ex(game.get_font(), item_btn_title, btn_padding, DEFAULT_MENU_ITEM_FONT_SIZE, game.get_font_spacing(), Color::BLACK);
+        // Daily challenge which was already played today is shown dimmed
+        let btn_text_color: Color = if menu_item.title == MenuAllItems::Daily && game.is_daily_completed() { Color::GRAY } else { Color::BLACK };
+        d.draw_text_ex(game.get_font(), &item_btn_title, btn_padding, DEFAULT_MENU_ITEM_FONT_SIZE, game.get_font_spacing(), btn_text_color);
         
         // Draw item description
         match menu_item.title.description() {
             "" => {},
-            x => d.draw_text_ex(game.get_font(), game.get_locale().get(x).unwrap(), Vector2 {
+            x => d.draw_text_ex(game.get_font(), game.get_locale().get(x), Vector2 {
                 x: game.get_window_width() / 8.0,
                 y: btn_padding.y,
             }, DEFAULT_MENU_ITEM_FONT_SIZE, game.get_font_spacing(), Color::BLACK),
@@ -376,16 +506,28 @@ impl Menu {
     fn draw_language_selector(&self, d: &mut RaylibDrawHandle, game: &game::Game) {
         // Draw language icons
         for (index, locale) in game.get_all_locales().iter().enumerate() {
-            let texture: &Texture2D = locale.get_texture().as_ref().unwrap();
-            d.draw_texture_v(texture, Vector2 { 
-                x: self.locale_items[index].btn.x, 
-                y: self.locale_items[index].btn.y, 
-            }, Color::WHITE);
-            d.draw_rectangle_lines_ex(self.locale_items[index].btn, 10.0, self.locale_items[index].color);
+            let btn: Rectangle = self.locale_items[index].btn;
+            match locale.get_texture() {
+                Some(texture) => d.draw_texture_v(texture, Vector2 { 
+                    x: btn.x, 
+                    y: btn.y, 
+                }, Color::WHITE),
+                // Language without a flag is shown by its name
+                None => {
+                    d.draw_rectangle_rec(btn, Color::LIGHTGRAY);
+                    let text_sizes: Vector2 = game.get_font().measure_text(locale.get_language(), 36.0, game.get_font_spacing());
+                    d.draw_text_ex(game.get_font(), locale.get_language(), Vector2 {
+                        x: btn.x + (btn.width - text_sizes.x) / 2.0,
+                        y: btn.y + (btn.height - text_sizes.y) / 2.0,
+                    }, 36.0, game.get_font_spacing(), Color::BLACK);
+                },
+            }
+            let frame_color: Color = if self.focus_index == Some(index) { FOCUS_COLOR } else { self.locale_items[index].color };
+            d.draw_rectangle_lines_ex(self.locale_items[index].btn, 10.0, frame_color);
         }
 
         // Draw text
-        let text: String = format!("{} {}", game.get_locale().get(consts::CHOOSE_LANGUAGE_STRING_NAME).unwrap(), game.get_locale().get_language());
+        let text: String = format!("{} {}", game.get_locale().get(consts::CHOOSE_LANGUAGE_STRING_NAME), game.get_locale().get_language());
         draw_text_center(d, text.as_str(), self.locale_items[0].btn.y - 80.0, 48.0, Color::GREEN, &game);
     }
 
@@ -396,7 +538,7 @@ impl Menu {
         let mut max_help_text_index: usize = 0;
 
         for (index, row) in HELP_TEXT_ROWS.iter().enumerate() {
-            width = game.get_locale().get(row.content).unwrap().len();
+            width = Self::get_help_row_text(game, row).len();
             if width > max_width {
                 max_width = width;
                 max_help_text_index = index;
@@ -404,23 +546,124 @@ impl Menu {
         };
 
         // Calculate measures of help text on the screen
-        let text_sizes: Vector2 = game.get_font().measure_text(game.get_locale().get(&HELP_TEXT_ROWS[max_help_text_index].content).unwrap(), 20.0, game.get_font_spacing());
+        let text_sizes: Vector2 = game.get_font().measure_text(&Self::get_help_row_text(game, &HELP_TEXT_ROWS[max_help_text_index]), 20.0, game.get_font_spacing());
         let x: f32 = (game.get_window_width() - text_sizes.x) / 2.0;
-        let mut y: f32 = (game.get_window_height() - (100.0 + 64.0 * 3.0 + 36.0 * 4.0 + 32.0)) / 2.0;
+        let mut y: f32 = (game.get_window_height() - (100.0 + 64.0 * 3.0 + 36.0 * 5.0 + 32.0)) / 2.0;
 
         // Draw help text
         for item in HELP_TEXT_ROWS.iter() {
-            d.draw_text_ex(game.get_font(), game.get_locale().get(&item.content).unwrap(), Vector2 {x: x, y: y}, item.font_size as f32, game.get_font_spacing(), Color::BLACK);
+            d.draw_text_ex(game.get_font(), &Self::get_help_row_text(game, item), Vector2 {x: x, y: y}, item.font_size as f32, game.get_font_spacing(), Color::BLACK);
             y += item.padding_bottom as f32;
         }
     }
 
-    fn update_primary_menu(&mut self, game: &game::Game, items_count: i32) {
+    fn draw_records_menu(&self, d: &mut RaylibDrawHandle, game: &game::Game) {
+        // Table for the mode, difficulty and board size chosen for the next game
+        let key: RecordKey = RecordKey {
+            play_mode: game.get_play_mode(),
+            difficulty: game.get_difficulty(),
+            board_size: game.get_board_size(),
+        };
+        let records: Vec<Record> = game.get_records().get_top(key);
+        let title: String = game.get_locale().format(consts::RECORDS_TITLE_STRING_NAME, &[
+            ("mode", game.get_locale().get(key.play_mode.repr())),
+            ("difficulty", game.get_locale().get(key.difficulty.repr())),
+            ("size", &key.board_size.to_string()),
+        ]);
+        draw_text_center(d, title.as_str(), 80.0, 48.0, Color::BLACK, &game);
+
+        if records.is_empty() {
+            draw_text_center(d, game.get_locale().get(consts::RECORDS_EMPTY_STRING_NAME), game.get_window_height() / 2.0, 36.0, Color::DARKGRAY, &game);
+        } else {
+            let column_width: f32 = game.get_window_width() / 7.0;
+            let headers: [&str; 6] = [
+                "#",
+                game.get_locale().get(consts::RECORDS_SCORE_STRING_NAME),
+                game.get_locale().get(consts::RECORDS_FAILS_STRING_NAME),
+                game.get_locale().get(consts::RECORDS_TIME_STRING_NAME),
+                game.get_locale().get(consts::RECORDS_SEED_STRING_NAME),
+                game.get_locale().get(consts::RECORDS_DATE_STRING_NAME),
+            ];
+            let mut rows: Vec<[String; 6]> = Vec::with_capacity(records.len());
+            for (index, record) in records.iter().enumerate() {
+                rows.push([
+                    (index + 1).to_string(),
+                    record.score.to_string(),
+                    record.fails.to_string(),
+                    format_precise_time(record.time),
+                    record.seed.to_string(),
+                    record.date.to_string(),
+                ]);
+            }
+
+            for (column, header) in headers.iter().enumerate() {
+                d.draw_text_ex(game.get_font(), header, Vector2 {
+                    x: column_width * (column as f32 + 0.5),
+                    y: 180.0,
+                }, 36.0, game.get_font_spacing(), Color::DARKGRAY);
+            }
+            for (index, row) in rows.iter().enumerate() {
+                for (column, value) in row.iter().enumerate() {
+                    d.draw_text_ex(game.get_font(), value, Vector2 {
+                        x: column_width * (column as f32 + 0.5),
+                        y: 240.0 + 50.0 * index as f32,
+                    }, 36.0, game.get_font_spacing(), if index == 0 { Color::ORANGE } else { Color::BLACK });
+                }
+            }
+        }
+
+        // Daily challenge has its own results, only the one of today is shown
+        let daily_text: Option<String> = match game.get_daily_attempt(daily::get_today()) {
+            Some(DailyAttempt::Finished(record)) => Some(game.get_locale().format(consts::DAILY_TODAY_STRING_NAME, &[
+                ("points", &record.score.to_string()),
+                ("fails", &record.fails.to_string()),
+                ("time", &format_precise_time(record.time)),
+            ])),
+            Some(DailyAttempt::Started) => Some(game.get_locale().get(consts::DAILY_UNFINISHED_STRING_NAME).to_string()),
+            None => None,
+        };
+        if let Some(text) = daily_text {
+            draw_text_center(d, text.as_str(), game.get_window_height() - 140.0, 32.0, Color::DARKGRAY, &game);
+        }
+
+        draw_text_center(d, game.get_locale().get(consts::HELP_TITLE_3_STRING_NAME), game.get_window_height() - 80.0, 32.0, Color::BLACK, &game);
+    }
+
+    fn get_help_row_text(game: &game::Game, row: &HelpTextRow) -> String {
+        // Help text follows the board size and sequence chosen for the next game
+        let rule: SequenceRule = game.get_sequence_rule();
+        let sequence: Vec<Tile> = rule.generate(game.get_board_size().count());
+        let content: &str = match row.content {
+            consts::HELP_TEXT_2_STRING_NAME if game.get_play_mode() == game::PlayMode::TimeAttack => consts::HELP_TEXT_2_TIME_ATTACK_STRING_NAME,
+            x => x,
+        };
+
+        game.get_locale().format(content, &[
+            ("rule", &Self::get_sequence_rule_title(game, rule)),
+            ("first", &sequence[0].number.to_string()),
+            ("second", &sequence[1].number.to_string()),
+            ("third", &sequence[2].number.to_string()),
+            ("last", &sequence[sequence.len() - 1].number.to_string()),
+        ])
+    }
+
+    fn get_sequence_rule_title(game: &game::Game, rule: SequenceRule) -> String {
+        match rule {
+            SequenceRule::Step(step) => game.get_locale().format(rule.repr(), &[("step", &step.to_string())]),
+            SequenceRule::Offset(start) => game.get_locale().format(rule.repr(), &[("start", &start.to_string())]),
+            _ => game.get_locale().get(rule.repr()).to_string(),
+        }
+    }
+
+    fn update_primary_menu(&mut self, game: &game::Game, items_count: usize) {
         // Updates menu with or without "Continue" button (to continue game)
-        if items_count == 4 {
+        let focused_title: Option<MenuAllItems> = self.focus_index.and_then(|x| self.items.get(x)).map(|x| x.title);
+        if items_count == Self::PRIMARY_ITEMS.len() {
             self.items = Self::construct_menu_items(&Self::PRIMARY_ITEMS, game);
-        } else if items_count == 5 {
+        } else if items_count == Self::FULL_PRIMARY_ITEMS.len() {
             self.items = Self::construct_menu_items(&Self::FULL_PRIMARY_ITEMS, game);
         }
+        // Items move when "Continue" comes or goes, so the focus follows its item rather than its place
+        self.focus_index = focused_title.and_then(|title| self.items.iter().position(|x| x.title == title));
     }
 }
\ No newline at end of file
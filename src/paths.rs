@@ -0,0 +1,172 @@
+use std::env;
+use std::path::{ Path, PathBuf };
+use std::sync::OnceLock;
+
+const APP_DIR_NAME: &str = "numbers-game";
+// File which has to exist in a folder to be taken as the assets folder
+const ASSETS_MARKER: &str = "locales/codes.xml";
+
+pub const CONFIG_DIR_ENV: &str = "NUMBERS_GAME_CONFIG_DIR";
+pub const DATA_DIR_ENV: &str = "NUMBERS_GAME_DATA_DIR";
+pub const ASSETS_DIR_ENV: &str = "NUMBERS_GAME_ASSETS_DIR";
+
+// Locale packs are folders inside "locales" of the data folder, each one with these files
+pub const LOCALE_PACK_FILE: &str = "pack.xml";
+pub const LOCALE_PACK_FLAG_FILE: &str = "flag.png";
+
+static ASSETS_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+pub fn get_config_dir() -> PathBuf {
+    resolve_config_dir(&get_env, env::consts::OS).unwrap_or_else(|| PathBuf::from("."))
+}
+
+pub fn get_data_dir() -> PathBuf {
+    resolve_data_dir(&get_env, env::consts::OS).unwrap_or_else(|| PathBuf::from("."))
+}
+
+pub fn get_config_file(name: &str) -> PathBuf {
+    get_config_dir().join(name)
+}
+
+pub fn get_data_file(name: &str) -> PathBuf {
+    get_data_dir().join(name)
+}
+
+pub fn get_assets_dir() -> &'static Path {
+    ASSETS_DIR.get_or_init(|| {
+        let candidates: Vec<PathBuf> = get_assets_candidates(&get_env, env::current_exe().ok().as_deref());
+        find_assets_dir(&candidates).unwrap_or_else(|| PathBuf::from("assets"))
+    })
+}
+
+pub fn get_asset_path(relative_path: &str) -> PathBuf {
+    get_assets_dir().join(relative_path)
+}
+
+pub fn get_locale_pack_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = match std::fs::read_dir(get_data_dir().join("locales")) {
+        Ok(entries) => entries.filter_map(|x| x.ok()).map(|x| x.path()).filter(|x| x.join(LOCALE_PACK_FILE).is_file()).collect(),
+        Err(_) => Vec::new(),
+    };
+    // Packs are applied in the order of their folder names, so the result doesn't depend on the file system
+    dirs.sort();
+    dirs
+}
+
+pub fn read_to_string(path: &Path, legacy_name: &str) -> std::io::Result<String> {
+    // Files saved by old versions into the working directory are still picked up, they are written to the new place on the next save
+    std::fs::read_to_string(path).or_else(|_| std::fs::read_to_string(legacy_name))
+}
+
+pub fn write(path: &Path, data: &str) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, data)
+}
+
+fn get_env(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|x| !x.is_empty())
+}
+
+fn resolve_config_dir(get_env: &dyn Fn(&str) -> Option<String>, os: &str) -> Option<PathBuf> {
+    if let Some(dir) = get_env(CONFIG_DIR_ENV) {
+        return Some(PathBuf::from(dir));
+    }
+
+    match os {
+        "windows" => get_env("APPDATA").map(|x| PathBuf::from(x).join(APP_DIR_NAME)),
+        "macos" => get_env("HOME").map(|x| PathBuf::from(x).join("Library/Application Support").join(APP_DIR_NAME)),
+        _ => get_env("XDG_CONFIG_HOME").map(PathBuf::from)
+            .or_else(|| get_env("HOME").map(|x| PathBuf::from(x).join(".config")))
+            .map(|x| x.join(APP_DIR_NAME)),
+    }
+}
+
+fn resolve_data_dir(get_env: &dyn Fn(&str) -> Option<String>, os: &str) -> Option<PathBuf> {
+    if let Some(dir) = get_env(DATA_DIR_ENV) {
+        return Some(PathBuf::from(dir));
+    }
+
+    match os {
+        "windows" => get_env("APPDATA").map(|x| PathBuf::from(x).join(APP_DIR_NAME)),
+        "macos" => get_env("HOME").map(|x| PathBuf::from(x).join("Library/Application Support").join(APP_DIR_NAME)),
+        _ => get_env("XDG_DATA_HOME").map(PathBuf::from)
+            .or_else(|| get_env("HOME").map(|x| PathBuf::from(x).join(".local/share")))
+            .map(|x| x.join(APP_DIR_NAME)),
+    }
+}
+
+fn get_assets_candidates(get_env: &dyn Fn(&str) -> Option<String>, exe_path: Option<&Path>) -> Vec<PathBuf> {
+    // Search order: env override, next to the executable, macOS bundle resources,
+    // system-wide install, project root for "cargo run", and the working directory
+    let mut candidates: Vec<PathBuf> = Vec::new();
+
+    if let Some(dir) = get_env(ASSETS_DIR_ENV) {
+        candidates.push(PathBuf::from(dir));
+    }
+    if let Some(exe_dir) = exe_path.and_then(|x| x.parent()) {
+        candidates.push(exe_dir.join("assets"));
+        candidates.push(exe_dir.join("../Resources/assets"));
+        candidates.push(exe_dir.join("../share").join(APP_DIR_NAME).join("assets"));
+        candidates.push(exe_dir.join("../../assets"));
+    }
+    candidates.push(PathBuf::from("assets"));
+
+    candidates
+}
+
+fn find_assets_dir(candidates: &[PathBuf]) -> Option<PathBuf> {
+    candidates.iter().find(|x| x.join(ASSETS_MARKER).is_file()).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn get_fake_env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        move |name: &str| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn resolves_xdg_dirs() {
+        let env = get_fake_env(&[("HOME", "/home/player"), ("XDG_DATA_HOME", "/data")]);
+
+        assert_eq!(resolve_config_dir(&env, "linux"), Some(PathBuf::from("/home/player/.config/numbers-game")));
+        assert_eq!(resolve_data_dir(&env, "linux"), Some(PathBuf::from("/data/numbers-game")));
+    }
+
+    #[test]
+    fn env_override_has_priority() {
+        let env = get_fake_env(&[("HOME", "/home/player"), (CONFIG_DIR_ENV, "/tmp/cfg"), (DATA_DIR_ENV, "/tmp/data")]);
+
+        assert_eq!(resolve_config_dir(&env, "linux"), Some(PathBuf::from("/tmp/cfg")));
+        assert_eq!(resolve_data_dir(&env, "windows"), Some(PathBuf::from("/tmp/data")));
+        assert_eq!(resolve_config_dir(&get_fake_env(&[]), "linux"), None);
+    }
+
+    #[test]
+    fn assets_are_searched_next_to_executable_first() {
+        let env = get_fake_env(&[(ASSETS_DIR_ENV, "/opt/assets")]);
+        let candidates: Vec<PathBuf> = get_assets_candidates(&env, Some(Path::new("/usr/bin/numbers-game")));
+
+        assert_eq!(candidates[0], PathBuf::from("/opt/assets"));
+        assert_eq!(candidates[1], PathBuf::from("/usr/bin/assets"));
+        assert_eq!(candidates[candidates.len() - 1], PathBuf::from("assets"));
+    }
+
+    #[test]
+    fn finds_first_folder_with_assets() {
+        let root: PathBuf = env::temp_dir().join(format!("numbers-game-paths-{}", std::process::id()));
+        let assets: PathBuf = root.join("second");
+        std::fs::create_dir_all(assets.join("locales")).unwrap();
+        std::fs::write(assets.join(ASSETS_MARKER), "<locales/>").unwrap();
+
+        let found: Option<PathBuf> = find_assets_dir(&[root.join("first"), assets.clone(), root.join("third")]);
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(found, Some(assets));
+    }
+}
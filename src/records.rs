@@ -0,0 +1,188 @@
+use chrono::NaiveDate;
+
+use crate::board::BoardSize;
+use crate::game::{ GameDifficulty, GameMode, PlayMode };
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RecordKey {
+    pub play_mode: PlayMode,
+    pub difficulty: GameDifficulty,
+    pub board_size: BoardSize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Record {
+    pub score: i32,
+    pub fails: i32,
+    pub time: f64,
+    pub seed: u64,
+    pub date: NaiveDate,
+}
+
+impl Record {
+    fn is_better_than(&self, other: &Record, play_mode: PlayMode) -> bool {
+        // Time attack is ranked by completion time, countdown by score, the other value breaks ties
+        match play_mode {
+            PlayMode::Countdown => self.score > other.score || (self.score == other.score && self.time < other.time),
+            PlayMode::TimeAttack => self.time < other.time || (self.time == other.time && self.score > other.score),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct RecordTable {
+    entries: Vec<(RecordKey, Record)>,
+}
+
+impl RecordTable {
+    pub const MAX_ENTRIES_PER_KEY: usize = 10;
+
+    pub fn parse(data: &str) -> Self {
+        // Broken lines are skipped, so one bad line doesn't wipe out the whole table
+        let mut table: Self = Self::default();
+        for line in data.lines() {
+            if let Some((key, record)) = Self::parse_line(line) {
+                table.add(key, record);
+            }
+        }
+        table
+    }
+
+    fn parse_line(line: &str) -> Option<(RecordKey, Record)> {
+        let values: Vec<&str> = line.split(',').map(|x| x.trim()).collect();
+        if values.len() != 8 {
+            return None;
+        }
+
+        let key: RecordKey = RecordKey {
+            play_mode: values[0].parse::<PlayMode>().ok()?,
+            difficulty: values[1].parse::<GameDifficulty>().ok()?,
+            board_size: values[2].parse::<BoardSize>().ok()?,
+        };
+        let record: Record = Record {
+            score: values[3].parse::<i32>().ok()?,
+            fails: values[4].parse::<i32>().ok()?,
+            time: values[5].parse::<f64>().ok()?,
+            seed: values[6].parse::<u64>().ok()?,
+            date: values[7].parse::<NaiveDate>().ok()?,
+        };
+        Some((key, record))
+    }
+
+    pub fn serialize(&self) -> String {
+        self.entries.iter()
+            .map(|(key, record)| format!("{},{},{},{},{},{:.3},{},{}",
+                                         key.play_mode, key.difficulty, key.board_size,
+                                         record.score, record.fails, record.time, record.seed, record.date))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    pub fn get_top(&self, key: RecordKey) -> Vec<Record> {
+        self.entries.iter().filter(|(x, _)| *x == key).map(|(_, record)| *record).collect()
+    }
+
+    // Returns place of the record in its table (0 is the best one) or None if it is not good enough to be kept
+    pub fn add(&mut self, key: RecordKey, record: Record) -> Option<usize> {
+        let top: Vec<Record> = self.get_top(key);
+        let place: usize = top.iter().position(|x| record.is_better_than(x, key.play_mode)).unwrap_or(top.len());
+        if place >= Self::MAX_ENTRIES_PER_KEY {
+            return None;
+        }
+
+        // Entries of the same key are kept sorted, so the new one goes right before the entry it beats
+        let index: usize = match top.get(place) {
+            Some(worse) => self.entries.iter().position(|(x, y)| *x == key && y == worse).unwrap(),
+            None => self.entries.len(),
+        };
+        self.entries.insert(index, (key, record));
+
+        if top.len() >= Self::MAX_ENTRIES_PER_KEY {
+            let last: Record = top[top.len() - 1];
+            let last_index: usize = self.entries.iter().rposition(|(x, y)| *x == key && *y == last).unwrap();
+            self.entries.remove(last_index);
+        }
+
+        Some(place)
+    }
+
+    // Debug runs have shorter timers and can be finished from the console, so they never get into the table
+    pub fn add_run(&mut self, mode: GameMode, key: RecordKey, record: Record) -> Option<usize> {
+        if mode == GameMode::Debug {
+            return None;
+        }
+        self.add(key, record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: RecordKey = RecordKey {
+        play_mode: PlayMode::Countdown,
+        difficulty: GameDifficulty::Medium,
+        board_size: BoardSize::new(8, 7),
+    };
+
+    fn record(score: i32, time: f64) -> Record {
+        Record {
+            score: score,
+            fails: 1,
+            time: time,
+            seed: 42,
+            date: NaiveDate::from_ymd_opt(2025, 3, 14).unwrap(),
+        }
+    }
+
+    #[test]
+    fn keeps_records_sorted_per_key() {
+        let mut table: RecordTable = RecordTable::default();
+        let time_attack_key: RecordKey = RecordKey { play_mode: PlayMode::TimeAttack, ..KEY };
+
+        assert_eq!(table.add(KEY, record(100, 60.0)), Some(0));
+        assert_eq!(table.add(KEY, record(300, 60.0)), Some(0));
+        assert_eq!(table.add(KEY, record(200, 60.0)), Some(1));
+        assert_eq!(table.add(time_attack_key, record(100, 30.0)), Some(0));
+        assert_eq!(table.add(time_attack_key, record(300, 40.0)), Some(1));
+
+        let scores: Vec<i32> = table.get_top(KEY).iter().map(|x| x.score).collect();
+        assert_eq!(scores, vec![300, 200, 100]);
+        assert_eq!(table.get_top(time_attack_key)[0].time, 30.0);
+    }
+
+    #[test]
+    fn drops_records_beyond_limit() {
+        let mut table: RecordTable = RecordTable::default();
+        for score in 1..=RecordTable::MAX_ENTRIES_PER_KEY as i32 {
+            table.add(KEY, record(score * 10, 60.0));
+        }
+
+        assert_eq!(table.add(KEY, record(5, 60.0)), None);
+        assert_eq!(table.add(KEY, record(55, 60.0)), Some(5));
+        assert_eq!(table.get_top(KEY).len(), RecordTable::MAX_ENTRIES_PER_KEY);
+        assert_eq!(table.get_top(KEY)[RecordTable::MAX_ENTRIES_PER_KEY - 1].score, 20);
+    }
+
+    #[test]
+    fn skips_debug_runs() {
+        let mut table: RecordTable = RecordTable::default();
+
+        assert_eq!(table.add_run(GameMode::Debug, KEY, record(900, 1.0)), None);
+        assert!(table.get_top(KEY).is_empty());
+        assert_eq!(table.add_run(GameMode::Release, KEY, record(100, 60.0)), Some(0));
+        assert_eq!(table.get_top(KEY).len(), 1);
+    }
+
+    #[test]
+    fn survives_serialization() {
+        let mut table: RecordTable = RecordTable::default();
+        table.add(KEY, record(250, 42.137));
+        table.add(KEY, record(120, 60.0));
+
+        let data: String = format!("{}\nbroken line\n", table.serialize());
+        let parsed: RecordTable = RecordTable::parse(&data);
+
+        assert_eq!(parsed.get_top(KEY), table.get_top(KEY));
+    }
+}
@@ -0,0 +1,81 @@
+// SplitMix64 generator. Implemented here instead of using rand's StdRng, because boards
+// generated from the same seed have to stay identical across platforms and releases
+#[derive(Clone, Debug)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: seed,
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z: u64 = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    pub fn next_below(&mut self, bound: u64) -> u64 {
+        // Rejection sampling keeps the distribution uniform for bounds that are not powers of two
+        let zone: u64 = u64::MAX - u64::MAX % bound;
+        loop {
+            let value: u64 = self.next_u64();
+            if value < zone {
+                return value % bound;
+            }
+        }
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        // Fisher-Yates shuffle
+        for i in (1..items.len()).rev() {
+            let j: usize = self.next_below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+pub fn generate_seed() -> u64 {
+    // Seeds are kept short, so they are easy to read from the screen and share
+    rand::random::<u64>() % 1_000_000_000
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_splitmix64_reference_output() {
+        let mut rng: SeededRng = SeededRng::new(1234567);
+
+        assert_eq!(rng.next_u64(), 6457827717110365317);
+        assert_eq!(rng.next_u64(), 3203168211198807973);
+        assert_eq!(rng.next_u64(), 9817491932198370423);
+    }
+
+    #[test]
+    fn next_below_stays_in_range() {
+        let mut rng: SeededRng = SeededRng::new(42);
+
+        for bound in 1..100 {
+            assert!(rng.next_below(bound) < bound);
+        }
+    }
+
+    #[test]
+    fn shuffle_is_reproducible() {
+        let mut first: Vec<i32> = (1..=56).collect();
+        let mut second: Vec<i32> = (1..=56).collect();
+        SeededRng::new(2025).shuffle(&mut first);
+        SeededRng::new(2025).shuffle(&mut second);
+
+        assert_eq!(first, second);
+        assert_ne!(first, (1..=56).collect::<Vec<i32>>());
+    }
+}
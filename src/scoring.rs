@@ -0,0 +1,164 @@
+// Scoring models get click events with the time passed since the level start (pauses excluded),
+// so the same run always gets the same score and models can be tested without a window
+pub trait ScoringModel {
+    fn on_correct(&mut self, elapsed_time: f64);
+    // Penalty is the time the fail adds to the elapsed time (the stopwatch of time attack), it isn't the player's slowness
+    fn on_wrong(&mut self, elapsed_time: f64, penalty: f64);
+    fn reset(&mut self);
+    fn get_breakdown(&self) -> ScoreBreakdown;
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ScoreBreakdown {
+    pub correct_clicks: i32,
+    pub base_points: i32,
+    pub best_streak: i32,
+    pub streak_bonus: i32,
+    pub speed_bonus: i32,
+    pub fails: i32,
+    pub fail_penalty: i32,
+}
+
+impl ScoreBreakdown {
+    pub fn get_total(&self) -> i32 {
+        (self.base_points + self.streak_bonus + self.speed_bonus - self.fail_penalty).max(0)
+    }
+}
+
+pub fn default_model() -> Box<dyn ScoringModel> {
+    Box::new(StreakScoring::new())
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct StreakScoring {
+    breakdown: ScoreBreakdown,
+    streak: i32,
+    last_correct_time: f64,
+}
+
+impl StreakScoring {
+    pub const POINTS_PER_CLICK: i32 = 10;
+    // Every STREAK_STEP correct clicks in a row add one more multiplier to the click points
+    pub const STREAK_STEP: i32 = 5;
+    pub const MAX_STREAK_MULTIPLIER: i32 = 4;
+    // Click made right after the previous one gets the full bonus, which goes down to zero at SPEED_BONUS_WINDOW
+    pub const MAX_SPEED_BONUS: f64 = 10.0;
+    pub const SPEED_BONUS_WINDOW: f64 = 3.0;
+    pub const FAIL_PENALTY: i32 = 15;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn get_streak_multiplier(streak: i32) -> i32 {
+        (1 + (streak - 1) / Self::STREAK_STEP).min(Self::MAX_STREAK_MULTIPLIER)
+    }
+
+    fn get_speed_bonus(interval: f64) -> i32 {
+        let ratio: f64 = 1.0 - interval.max(0.0) / Self::SPEED_BONUS_WINDOW;
+        (Self::MAX_SPEED_BONUS * ratio.max(0.0)).round() as i32
+    }
+}
+
+impl ScoringModel for StreakScoring {
+    fn on_correct(&mut self, elapsed_time: f64) {
+        self.streak += 1;
+
+        let multiplier: i32 = Self::get_streak_multiplier(self.streak);
+        self.breakdown.correct_clicks += 1;
+        self.breakdown.base_points += Self::POINTS_PER_CLICK;
+        self.breakdown.streak_bonus += Self::POINTS_PER_CLICK * (multiplier - 1);
+        self.breakdown.best_streak = self.breakdown.best_streak.max(self.streak);
+        self.breakdown.speed_bonus += Self::get_speed_bonus(elapsed_time - self.last_correct_time);
+        self.last_correct_time = elapsed_time;
+    }
+
+    fn on_wrong(&mut self, _elapsed_time: f64, penalty: f64) {
+        self.streak = 0;
+        self.last_correct_time += penalty;
+        self.breakdown.fails += 1;
+        self.breakdown.fail_penalty += Self::FAIL_PENALTY;
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    fn get_breakdown(&self) -> ScoreBreakdown {
+        self.breakdown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streak_raises_click_points() {
+        let mut model: StreakScoring = StreakScoring::new();
+        for i in 0..6 {
+            model.on_correct(i as f64 * 10.0);
+        }
+
+        let breakdown: ScoreBreakdown = model.get_breakdown();
+        assert_eq!(breakdown.base_points, 60);
+        assert_eq!(breakdown.streak_bonus, 10);
+        assert_eq!(breakdown.best_streak, 6);
+        assert_eq!(breakdown.speed_bonus, 10);
+    }
+
+    #[test]
+    fn wrong_click_breaks_streak() {
+        let mut model: StreakScoring = StreakScoring::new();
+        for i in 0..5 {
+            model.on_correct(i as f64 * 10.0 + 10.0);
+        }
+        model.on_wrong(60.0, 0.0);
+        model.on_correct(70.0);
+
+        let breakdown: ScoreBreakdown = model.get_breakdown();
+        assert_eq!(breakdown.streak_bonus, 0);
+        assert_eq!(breakdown.best_streak, 5);
+        assert_eq!(breakdown.fails, 1);
+        assert_eq!(breakdown.get_total(), 60 - StreakScoring::FAIL_PENALTY);
+    }
+
+    #[test]
+    fn faster_run_scores_more() {
+        let mut fast: StreakScoring = StreakScoring::new();
+        let mut slow: StreakScoring = StreakScoring::new();
+        for i in 1..=10 {
+            fast.on_correct(i as f64 * 0.5);
+            slow.on_correct(i as f64 * 2.5);
+        }
+
+        assert!(fast.get_breakdown().speed_bonus > slow.get_breakdown().speed_bonus);
+        assert_eq!(fast.get_breakdown().base_points, slow.get_breakdown().base_points);
+    }
+
+    #[test]
+    fn penalty_does_not_cancel_speed_bonus() {
+        let mut penalized: StreakScoring = StreakScoring::new();
+        let mut clean: StreakScoring = StreakScoring::new();
+
+        // Time attack penalty moves the stopwatch forward, the next click is still made 1s after the previous one
+        penalized.on_correct(1.0);
+        penalized.on_wrong(1.5, 3.0);
+        penalized.on_correct(2.0 + 3.0);
+        clean.on_correct(1.0);
+        clean.on_wrong(1.5, 0.0);
+        clean.on_correct(2.0);
+
+        assert_eq!(penalized.get_breakdown().speed_bonus, clean.get_breakdown().speed_bonus);
+        assert_eq!(penalized.get_breakdown().fail_penalty, StreakScoring::FAIL_PENALTY);
+    }
+
+    #[test]
+    fn total_is_never_negative() {
+        let mut model: StreakScoring = StreakScoring::new();
+        model.on_wrong(1.0, 0.0);
+        model.on_wrong(2.0, 0.0);
+
+        assert_eq!(model.get_breakdown().get_total(), 0);
+    }
+}
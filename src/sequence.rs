@@ -0,0 +1,168 @@
+use crate::board::Tile;
+use crate::consts;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SequenceRule {
+    Ascending,
+    Descending,
+    Step(i32),
+    Offset(i32),
+    Alternating,
+}
+
+impl std::fmt::Display for SequenceRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            Self::Ascending => write!(f, "ascending"),
+            Self::Descending => write!(f, "descending"),
+            Self::Step(step) => write!(f, "step:{}", step),
+            Self::Offset(start) => write!(f, "offset:{}", start),
+            Self::Alternating => write!(f, "alternating"),
+        }
+    }
+}
+
+impl std::str::FromStr for SequenceRule {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value: &str = value.trim();
+        let rule: Self = match value.split_once(":") {
+            None if value == "ascending" => Self::Ascending,
+            None if value == "descending" => Self::Descending,
+            None if value == "alternating" => Self::Alternating,
+            Some(("step", x)) => Self::Step(x.trim().parse::<i32>().map_err(|e| e.to_string())?),
+            Some(("offset", x)) => Self::Offset(x.trim().parse::<i32>().map_err(|e| e.to_string())?),
+            _ => return Err(format!("Unknown sequence rule: {}", value)),
+        };
+
+        if rule.is_valid() { Ok(rule) } else { Err(format!("Sequence rule out of range: {}", value)) }
+    }
+}
+
+impl SequenceRule {
+    pub const MAX_STEP: i32 = 10;
+    pub const MAX_OFFSET: i32 = 900;
+    pub const PRESETS: [SequenceRule; 7] = [
+        SequenceRule::Ascending,
+        SequenceRule::Descending,
+        SequenceRule::Step(2),
+        SequenceRule::Step(3),
+        SequenceRule::Step(5),
+        SequenceRule::Offset(21),
+        SequenceRule::Alternating,
+    ];
+
+    pub fn repr(&self) -> &str {
+        match *self {
+            Self::Ascending => consts::SEQUENCE_ASCENDING_STRING_NAME,
+            Self::Descending => consts::SEQUENCE_DESCENDING_STRING_NAME,
+            Self::Step(_) => consts::SEQUENCE_STEP_STRING_NAME,
+            Self::Offset(_) => consts::SEQUENCE_OFFSET_STRING_NAME,
+            Self::Alternating => consts::SEQUENCE_ALTERNATING_STRING_NAME,
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            Self::Step(step) => (1..=Self::MAX_STEP).contains(&step),
+            Self::Offset(start) => (0..=Self::MAX_OFFSET).contains(&start),
+            _ => true,
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        // Custom rules from the config file are not in presets, so cycling starts over from the first preset
+        match Self::PRESETS.iter().position(|x| x == self) {
+            Some(index) if index + 1 < Self::PRESETS.len() => Self::PRESETS[index + 1],
+            _ => Self::PRESETS[0],
+        }
+    }
+
+    pub fn prev(&self) -> Self {
+        match Self::PRESETS.iter().position(|x| x == self) {
+            Some(index) if index > 0 => Self::PRESETS[index - 1],
+            _ => Self::PRESETS[Self::PRESETS.len() - 1],
+        }
+    }
+
+    // Tiles of the board in the order they have to be clicked
+    pub fn generate(&self, count: i32) -> Vec<Tile> {
+        match *self {
+            Self::Ascending => (1..=count).map(Tile::black).collect(),
+            Self::Descending => (1..=count).rev().map(Tile::black).collect(),
+            Self::Step(step) => (1..=count).map(|x| Tile::black(x * step)).collect(),
+            Self::Offset(start) => (start..start + count).map(Tile::black).collect(),
+            Self::Alternating => Self::generate_alternating(count),
+        }
+    }
+
+    fn generate_alternating(count: i32) -> Vec<Tile> {
+        // Gorbov-Schulte table: black numbers go up, red numbers go down, and the player switches between them
+        let black_count: i32 = (count + 1) / 2;
+        let red_count: i32 = count / 2;
+        let mut tiles: Vec<Tile> = Vec::with_capacity(count as usize);
+
+        for index in 0..black_count {
+            tiles.push(Tile::black(index + 1));
+            if index < red_count {
+                tiles.push(Tile::red(red_count - index));
+            }
+        }
+
+        tiles
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::TileColor;
+
+    #[test]
+    fn generates_sequences() {
+        assert_eq!(SequenceRule::Ascending.generate(4), vec![Tile::black(1), Tile::black(2), Tile::black(3), Tile::black(4)]);
+        assert_eq!(SequenceRule::Descending.generate(4), vec![Tile::black(4), Tile::black(3), Tile::black(2), Tile::black(1)]);
+        assert_eq!(SequenceRule::Step(3).generate(4), vec![Tile::black(3), Tile::black(6), Tile::black(9), Tile::black(12)]);
+        assert_eq!(SequenceRule::Offset(10).generate(4), vec![Tile::black(10), Tile::black(11), Tile::black(12), Tile::black(13)]);
+    }
+
+    #[test]
+    fn generates_alternating_sequence() {
+        assert_eq!(SequenceRule::Alternating.generate(5), vec![Tile::black(1), Tile::red(2), Tile::black(2), Tile::red(1), Tile::black(3)]);
+
+        let tiles: Vec<Tile> = SequenceRule::Alternating.generate(49);
+        assert_eq!(tiles.len(), 49);
+        assert_eq!(tiles.iter().filter(|x| x.color == TileColor::Red).count(), 24);
+        assert_eq!(tiles[tiles.len() - 1], Tile::black(25));
+    }
+
+    #[test]
+    fn parses_and_prints_rules() {
+        for rule in SequenceRule::PRESETS.iter() {
+            assert_eq!(rule.to_string().parse::<SequenceRule>(), Ok(*rule));
+        }
+        assert_eq!(" step: 4 ".parse::<SequenceRule>(), Ok(SequenceRule::Step(4)));
+        assert!("step:0".parse::<SequenceRule>().is_err());
+        assert!("offset:-1".parse::<SequenceRule>().is_err());
+        assert!("zigzag".parse::<SequenceRule>().is_err());
+    }
+
+    #[test]
+    fn next_cycles_through_presets() {
+        assert_eq!(SequenceRule::Ascending.next(), SequenceRule::Descending);
+        assert_eq!(SequenceRule::Offset(21).next(), SequenceRule::Alternating);
+        assert_eq!(SequenceRule::Alternating.next(), SequenceRule::Ascending);
+        assert_eq!(SequenceRule::Step(7).next(), SequenceRule::Ascending);
+    }
+
+    #[test]
+    fn prev_cycles_through_presets_backwards() {
+        assert_eq!(SequenceRule::Descending.prev(), SequenceRule::Ascending);
+        assert_eq!(SequenceRule::Ascending.prev(), SequenceRule::Alternating);
+        assert_eq!(SequenceRule::Step(7).prev(), SequenceRule::Alternating);
+        for rule in SequenceRule::PRESETS.iter() {
+            assert_eq!(rule.next().prev(), *rule);
+        }
+    }
+}
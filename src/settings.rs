@@ -0,0 +1,285 @@
+use crate::board::BoardSize;
+use crate::game::{ GameDifficulty, PlayMode };
+use crate::sequence::SequenceRule;
+
+// Version 1 is the old format without "version" line, where locale is stored as an index
+// into the list of locales and difficulty as a number. Files of newer versions are read as the current one
+pub const SETTINGS_VERSION: u32 = 2;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Settings {
+    pub locale: String,
+    pub difficulty: GameDifficulty,
+    pub play_mode: PlayMode,
+    pub board_size: Option<BoardSize>,
+    pub sequence_rule: SequenceRule,
+    pub seed: Option<u64>,
+    pub is_fullscreen: bool,
+    pub is_vsync: bool,
+    pub is_fps_visible: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            locale: "en".to_string(),
+            difficulty: GameDifficulty::Easy,
+            play_mode: PlayMode::Countdown,
+            board_size: None,
+            sequence_rule: SequenceRule::Ascending,
+            seed: None,
+            is_fullscreen: true,
+            is_vsync: true,
+            is_fps_visible: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SettingsError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+// Values given on the command line, they are used for one session and never written to the config file
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SettingsOverrides {
+    pub locale: Option<String>,
+    pub difficulty: Option<GameDifficulty>,
+    pub is_fullscreen: Option<bool>,
+}
+
+impl SettingsOverrides {
+    pub fn apply(&self, settings: &Settings) -> Settings {
+        Settings {
+            locale: self.locale.clone().unwrap_or_else(|| settings.locale.clone()),
+            difficulty: self.difficulty.unwrap_or(settings.difficulty),
+            is_fullscreen: self.is_fullscreen.unwrap_or(settings.is_fullscreen),
+            ..settings.clone()
+        }
+    }
+
+    // Settings to save: overridden values are taken from the saved ones, everything else from the current ones
+    pub fn restore(&self, settings: &Settings, saved: &Settings) -> Settings {
+        Settings {
+            locale: if self.locale.is_some() { saved.locale.clone() } else { settings.locale.clone() },
+            difficulty: if self.difficulty.is_some() { saved.difficulty } else { settings.difficulty },
+            is_fullscreen: if self.is_fullscreen.is_some() { saved.is_fullscreen } else { settings.is_fullscreen },
+            ..settings.clone()
+        }
+    }
+}
+
+pub struct LoadedSettings {
+    pub settings: Settings,
+    pub version: u32,
+    pub errors: Vec<SettingsError>,
+}
+
+impl Settings {
+    // Locale codes are needed to migrate locale index of the old format into a code
+    pub fn parse(data: &str, locale_codes: &[String]) -> LoadedSettings {
+        let mut settings: Self = Self::default();
+        let mut errors: Vec<SettingsError> = Vec::new();
+        let mut lines: Vec<(usize, &str, &str)> = Vec::new();
+
+        for (index, line) in data.lines().enumerate() {
+            let line: &str = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match line.split_once('=') {
+                Some((key, value)) => lines.push((index + 1, key.trim(), value.trim())),
+                None => errors.push(SettingsError {
+                    line: index + 1,
+                    message: format!("expected \"key = value\", found \"{}\"", line),
+                }),
+            }
+        }
+
+        let mut version: u32 = 1;
+        if let Some((line, _, value)) = lines.iter().find(|(_, key, _)| *key == "version") {
+            match value.parse::<u32>() {
+                Ok(x) if x <= SETTINGS_VERSION => version = x,
+                Ok(x) => {
+                    version = x;
+                    errors.push(SettingsError {
+                        line: *line,
+                        message: format!("version {} is newer than supported version {}, the file is not changed", x, SETTINGS_VERSION),
+                    });
+                },
+                Err(_) => errors.push(SettingsError {
+                    line: *line,
+                    message: format!("invalid version \"{}\"", value),
+                }),
+            }
+        }
+
+        for (line, key, value) in lines {
+            let result: Result<(), String> = match key {
+                "version" => Ok(()),
+                "locale" => Self::parse_locale(value, version, locale_codes).map(|x| settings.locale = x),
+                "difficulty" => Self::parse_difficulty(value, version).map(|x| settings.difficulty = x),
+                "play_mode" => value.parse::<PlayMode>().map(|x| settings.play_mode = x),
+                "board_size" => match value {
+                    "auto" => {
+                        settings.board_size = None;
+                        Ok(())
+                    },
+                    x => x.parse::<BoardSize>().map(|x| settings.board_size = Some(x)),
+                },
+                "sequence" => value.parse::<SequenceRule>().map(|x| settings.sequence_rule = x),
+                "seed" => value.parse::<u64>().map(|x| settings.seed = Some(x)).map_err(|e| e.to_string()),
+                // Daily progress moved to the data folder, the date saved by older versions is dropped
+                "daily" => Ok(()),
+                "fullscreen" => Self::parse_bool(value).map(|x| settings.is_fullscreen = x),
+                "vsync" => Self::parse_bool(value).map(|x| settings.is_vsync = x),
+                "fps_counter" => Self::parse_bool(value).map(|x| settings.is_fps_visible = x),
+                _ => Err(format!("unknown setting \"{}\"", key)),
+            };
+
+            if let Err(message) = result {
+                errors.push(SettingsError {
+                    line: line,
+                    message: format!("{} ({} = {})", message, key, value),
+                });
+            }
+        }
+
+        LoadedSettings {
+            settings: settings,
+            version: version,
+            errors: errors,
+        }
+    }
+
+    fn parse_locale(value: &str, version: u32, locale_codes: &[String]) -> Result<String, String> {
+        let code: String = if version == 1 {
+            let index: usize = value.parse::<usize>().map_err(|e| e.to_string())?;
+            locale_codes.get(index).ok_or(format!("no locale with index {}", index))?.clone()
+        } else {
+            value.to_string()
+        };
+
+        if locale_codes.contains(&code) { Ok(code) } else { Err(format!("unknown locale \"{}\"", code)) }
+    }
+
+    fn parse_difficulty(value: &str, version: u32) -> Result<GameDifficulty, String> {
+        if version > 1 {
+            return value.parse::<GameDifficulty>();
+        }
+
+        match value.parse::<i32>() {
+            Ok(x) if x == GameDifficulty::Easy as i32 => Ok(GameDifficulty::Easy),
+            Ok(x) if x == GameDifficulty::Medium as i32 => Ok(GameDifficulty::Medium),
+            Ok(x) if x == GameDifficulty::Hard as i32 => Ok(GameDifficulty::Hard),
+            _ => Err(format!("unknown difficulty \"{}\"", value)),
+        }
+    }
+
+    fn parse_bool(value: &str) -> Result<bool, String> {
+        value.parse::<bool>().map_err(|_| format!("expected true or false, found \"{}\"", value))
+    }
+
+    pub fn serialize(&self) -> String {
+        let board_size: String = match self.board_size {
+            Some(size) => size.to_string(),
+            None => "auto".to_string(),
+        };
+        let mut data: String = format!(
+            "version = {}\nlocale = {}\ndifficulty = {}\nplay_mode = {}\nboard_size = {}\nsequence = {}\nfullscreen = {}\nvsync = {}\nfps_counter = {}\n",
+            SETTINGS_VERSION, self.locale, self.difficulty, self.play_mode, board_size, self.sequence_rule,
+            self.is_fullscreen, self.is_vsync, self.is_fps_visible
+        );
+        if let Some(seed) = self.seed {
+            data.push_str(&format!("seed = {}\n", seed));
+        }
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_locale_codes() -> Vec<String> {
+        vec!["en".to_string(), "ua".to_string(), "de".to_string()]
+    }
+
+    #[test]
+    fn survives_serialization() {
+        let settings: Settings = Settings {
+            locale: "de".to_string(),
+            difficulty: GameDifficulty::Hard,
+            play_mode: PlayMode::TimeAttack,
+            board_size: Some(BoardSize::new(9, 9)),
+            sequence_rule: SequenceRule::Step(3),
+            seed: Some(20251018),
+            is_fullscreen: false,
+            is_vsync: false,
+            is_fps_visible: true,
+        };
+        let loaded: LoadedSettings = Settings::parse(&settings.serialize(), &get_locale_codes());
+
+        assert_eq!(loaded.settings, settings);
+        assert_eq!(loaded.version, SETTINGS_VERSION);
+        assert!(loaded.errors.is_empty());
+    }
+
+    #[test]
+    fn migrates_old_format() {
+        let loaded: LoadedSettings = Settings::parse("locale = 1\ndifficulty = 2", &get_locale_codes());
+
+        assert_eq!(loaded.version, 1);
+        assert_eq!(loaded.settings.locale, "ua");
+        assert_eq!(loaded.settings.difficulty, GameDifficulty::Hard);
+        assert!(loaded.errors.is_empty());
+    }
+
+    #[test]
+    fn reads_newer_format_as_current() {
+        let data: String = format!("version = 3\nlocale = de\ndifficulty = {}\ntheme = dark", GameDifficulty::Hard);
+        let loaded: LoadedSettings = Settings::parse(&data, &get_locale_codes());
+
+        assert!(loaded.version > SETTINGS_VERSION);
+        assert_eq!(loaded.settings.locale, "de");
+        assert_eq!(loaded.settings.difficulty, GameDifficulty::Hard);
+        assert_eq!(loaded.errors.iter().map(|x| x.line).collect::<Vec<usize>>(), vec![1, 4]);
+    }
+
+    #[test]
+    fn overrides_are_not_saved() {
+        let saved: Settings = Settings::default();
+        let overrides: SettingsOverrides = SettingsOverrides {
+            locale: Some("ua".to_string()),
+            difficulty: Some(GameDifficulty::Hard),
+            is_fullscreen: None,
+        };
+
+        let mut settings: Settings = overrides.apply(&saved);
+        assert_eq!(settings.locale, "ua");
+        assert_eq!(settings.difficulty, GameDifficulty::Hard);
+
+        settings.is_fullscreen = false;
+        let restored: Settings = overrides.restore(&settings, &saved);
+        assert_eq!(restored, Settings { is_fullscreen: false, ..saved });
+    }
+
+    #[test]
+    fn reports_malformed_lines() {
+        let data: &str = "version = 2\nlocale = fr\nfullscreen = maybe\nvsync = false\ncolour = blue\nbroken line";
+        let loaded: LoadedSettings = Settings::parse(data, &get_locale_codes());
+        let lines: Vec<usize> = loaded.errors.iter().map(|x| x.line).collect();
+
+        assert_eq!(lines, vec![6, 2, 3, 5]);
+        assert_eq!(loaded.settings.locale, "en");
+        assert!(loaded.settings.is_fullscreen);
+        assert!(!loaded.settings.is_vsync);
+    }
+}
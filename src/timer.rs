@@ -4,24 +4,60 @@ use raylib::prelude::*;
 use crate::game;
 
 const START_DELAY_SECS: f64 = 0.1;
+const INDICATOR_LIFETIME_SECS: f64 = 1.0;
+const INDICATOR_RISE: f32 = 30.0;
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct TimeRules {
+    pub correct_click_bonus: f64,
+    pub wrong_click_penalty: f64,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum TimerMode {
+    Countdown,
+    Stopwatch,
+}
+
+struct TimeIndicator {
+    delta: f64,
+    created_at: f64,
+}
 
 pub struct Timer {
+    mode: TimerMode,
     is_running: bool,
     start_time: f64,
     pause_time: f64,
     duration: f64,
+    result_time: f64,
+    indicators: Vec<TimeIndicator>,
 }
 
 impl Timer {
     pub fn new(duration: i32) -> Self {
         Self {
+            mode: TimerMode::Countdown,
             is_running: false,
             start_time: 0.0,
             pause_time: 0.0,
             duration: duration as f64,
+            result_time: 0.0,
+            indicators: Vec::new(),
+        }
+    }
+
+    pub fn new_stopwatch() -> Self {
+        Self {
+            mode: TimerMode::Stopwatch,
+            ..Self::new(0)
         }
     }
 
+    pub fn get_mode(&self) -> TimerMode {
+        self.mode
+    }
+
     pub fn get_current_time_in_secs() -> f64 {
         SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs_f64()
     }
@@ -30,13 +66,32 @@ impl Timer {
         self.is_running
     }
 
+    pub fn get_start_time(&self) -> f64 {
+        self.start_time
+    }
+
+    pub fn get_pause_time(&self) -> f64 {
+        self.pause_time
+    }
+
+    pub fn get_duration(&self) -> f64 {
+        self.duration
+    }
+
     pub fn is_finished(&self) -> bool {
         !self.is_running && self.start_time == 0.0
     }
 
     pub fn is_over(&self) -> bool {
+        // Stopwatch has no time limit, the run ends only when the board is cleared
         let current_time: f64 = Self::get_current_time_in_secs();
-        self.is_running && current_time >= (self.start_time + self.duration)
+        self.mode == TimerMode::Countdown && self.is_running && current_time >= (self.start_time + self.duration)
+    }
+
+    pub fn get_elapsed_time(&self) -> f64 {
+        // Time of the current pause and the delay before activation are not counted
+        let current_time: f64 = if self.pause_time > 0.0 { self.pause_time } else { Self::get_current_time_in_secs() };
+        if self.start_time > 0.0 { (current_time - self.start_time - START_DELAY_SECS).max(0.0) } else { self.result_time }
     }
 
     pub fn activate(&mut self) {
@@ -64,14 +119,47 @@ impl Timer {
         }
     }
 
+    pub fn add_time(&mut self, secs: f64) {
+        // Negative value takes time away, the timer runs out on the next check if nothing is left
+        if secs == 0.0 || !self.is_running { return; }
+
+        let current_time: f64 = Self::get_current_time_in_secs();
+        match self.mode {
+            TimerMode::Countdown => self.duration += secs,
+            // Moving the start forward makes the stopwatch show less time
+            TimerMode::Stopwatch => self.start_time += secs,
+        }
+        self.indicators.retain(|x| current_time - x.created_at < INDICATOR_LIFETIME_SECS);
+        self.indicators.push(TimeIndicator {
+            delta: secs,
+            created_at: current_time,
+        });
+    }
+
+    pub fn set_time(&mut self, secs: f64) {
+        // Shown time becomes the given one: time left of a countdown or time passed of a stopwatch
+        if self.start_time == 0.0 { return; }
+
+        let current_time: f64 = if self.pause_time > 0.0 { self.pause_time } else { Self::get_current_time_in_secs() };
+        match self.mode {
+            TimerMode::Countdown => self.duration = current_time - self.start_time + secs.max(0.0),
+            TimerMode::Stopwatch => self.start_time = current_time - secs.max(0.0) - START_DELAY_SECS,
+        }
+    }
+
     pub fn finish(&mut self) {
+        self.result_time = self.get_elapsed_time();
         self.is_running = false;
         self.start_time = 0.0;
     }
 
     pub fn draw(&self, d: &mut RaylibDrawHandle, game: &game::Game, ) {
-        let left_time: i32 = if self.is_running { self.start_time + self.duration - Self::get_current_time_in_secs() } else { 0.0 } as i32;
-        let timer_str: String = format!("{0:0>2}:{1:0>2}", (left_time - left_time % 60) / 60, left_time % 60);
+        let current_time: f64 = Self::get_current_time_in_secs();
+        let shown_time: f64 = match self.mode {
+            TimerMode::Countdown => if self.is_running { (self.start_time + self.duration - current_time).max(0.0) } else { 0.0 },
+            TimerMode::Stopwatch => self.get_elapsed_time(),
+        };
+        let timer_str: String = format_time(shown_time);
         let text_sizes: Vector2 = game.get_font().measure_text(&timer_str, 48.0, game.get_font_spacing());
         let text_pos: Vector2 = Vector2 {
             x: game.get_window_width() - text_sizes.x - 16.0, 
@@ -79,5 +167,33 @@ impl Timer {
         };
 
         d.draw_text_ex(game.get_font(), &timer_str, text_pos, 48.0, game.get_font_spacing(), Color::BLACK);
+
+        // Floating "+1s" / "-3s" indicators rise and fade out next to the timer
+        for indicator in self.indicators.iter() {
+            let progress: f64 = (current_time - indicator.created_at) / INDICATOR_LIFETIME_SECS;
+            if progress >= 1.0 { continue; }
+
+            // Indicator shows how the time on the screen changes, so a stopwatch penalty is shown as "+3s"
+            let shown_delta: f64 = if self.mode == TimerMode::Stopwatch { -indicator.delta } else { indicator.delta };
+            let indicator_str: String = format!("{:+}s", shown_delta);
+            let indicator_sizes: Vector2 = game.get_font().measure_text(&indicator_str, 36.0, game.get_font_spacing());
+            let indicator_pos: Vector2 = Vector2 {
+                x: text_pos.x - indicator_sizes.x - 16.0,
+                y: text_pos.y + (text_sizes.y - indicator_sizes.y) / 2.0 - INDICATOR_RISE * progress as f32
+            };
+            let indicator_color: Color = if indicator.delta > 0.0 { Color::GREEN } else { Color::RED };
+
+            d.draw_text_ex(game.get_font(), &indicator_str, indicator_pos, 36.0, game.get_font_spacing(), indicator_color.alpha(1.0 - progress as f32));
+        }
     }
-}
\ No newline at end of file
+}
+
+pub fn format_time(secs: f64) -> String {
+    let secs: i32 = secs as i32;
+    format!("{0:0>2}:{1:0>2}", secs / 60, secs % 60)
+}
+
+pub fn format_precise_time(secs: f64) -> String {
+    let millis: i64 = (secs * 1000.0).round() as i64;
+    format!("{0:0>2}:{1:0>2}.{2:0>3}", millis / 60_000, millis / 1000 % 60, millis % 1000)
+}
@@ -0,0 +1,426 @@
+use std::collections::HashMap;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use crate::consts;
+
+// Locale which every other locale falls back to for keys it doesn't translate
+pub const BASE_LOCALE_CODE: &str = "en";
+
+#[derive(Clone, Debug, Default)]
+pub struct Translations {
+    values: HashMap<String, String>,
+    fallback: HashMap<String, String>,
+}
+
+impl Translations {
+    pub fn insert(&mut self, key: String, value: String) {
+        self.values.insert(key, value);
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.values.contains_key(key)
+    }
+
+    pub fn set_fallback(&mut self, base: &Translations) {
+        self.fallback = base.values.clone();
+    }
+
+    // Lookup order: own translation, base locale, and the key itself, so a partial translation never breaks the game
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        match self.values.get(key).or_else(|| self.fallback.get(key)) {
+            Some(value) => value,
+            None => key,
+        }
+    }
+
+    pub fn get_missing_keys(&self, keys: &[&str]) -> Vec<String> {
+        keys.iter().filter(|x| !self.contains(x)).map(|x| x.to_string()).collect()
+    }
+
+    // Every text the locale can show, including the ones taken from the base locale
+    pub fn get_texts(&self) -> impl Iterator<Item = &str> {
+        self.values.values().chain(self.fallback.values()).map(|x| x.as_str())
+    }
+}
+
+// Characters to put into a font atlas, so it holds only the glyphs the game can draw
+pub fn collect_glyphs<'a>(texts: impl Iterator<Item = &'a str>) -> String {
+    let mut glyphs: Vec<char> = consts::FONT_BASE_GLYPHS.chars().collect();
+    for text in texts {
+        glyphs.extend(text.chars().filter(|x| !x.is_control()));
+    }
+    glyphs.sort_unstable();
+    glyphs.dedup();
+    glyphs.into_iter().collect()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LanguageEntry {
+    pub code: String,
+    pub language: String,
+    // Font for scripts the default font doesn't cover, relative to the assets folder
+    pub font: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct XmlError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl XmlError {
+    fn new(data: &str, position: u64, message: String) -> Self {
+        let before: &str = &data[..(position as usize).min(data.len())];
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before.chars().rev().take_while(|&x| x != '\n').count() + 1,
+            message: message,
+        }
+    }
+}
+
+impl std::fmt::Display for XmlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+// Entries "<language code="en" font="fonts/x.ttf">English</language>" of codes.xml, the font is optional
+pub fn parse_language_list(data: &str) -> Result<Vec<LanguageEntry>, XmlError> {
+    Ok(parse_elements(data, b"language", b"code", Some(b"font"))?.into_iter()
+        .map(|(code, font, language)| LanguageEntry {
+            code: code,
+            language: language,
+            font: font,
+        })
+        .collect())
+}
+
+// Entries "<string name="key">value</string>" of a locale file as (key, value) in file order
+pub fn parse_strings(data: &str) -> Result<Vec<(String, String)>, XmlError> {
+    Ok(parse_elements(data, b"string", b"name", None)?.into_iter().map(|(key, _, value)| (key, value)).collect())
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LocalePack {
+    pub code: String,
+    // Name and font may be left out by a pack which only overrides strings of a bundled language
+    pub language: Option<String>,
+    pub font: Option<String>,
+    pub strings: Vec<(String, String)>,
+}
+
+// Locale pack file: strings of a locale file inside "<localization code="de-ch" language="Schweizerdeutsch" font="font.ttf">"
+pub fn parse_locale_pack(data: &str) -> Result<LocalePack, XmlError> {
+    let mut reader: Reader<&[u8]> = Reader::from_str(data);
+    let mut buf: Vec<u8> = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) if e.name().as_ref() == b"localization" => {
+                let position: u64 = reader.buffer_position();
+                return Ok(LocalePack {
+                    code: read_attribute(&e, b"code").map_err(|x| XmlError::new(data, position, x))?,
+                    language: read_attribute(&e, b"language").ok(),
+                    font: read_attribute(&e, b"font").ok(),
+                    strings: parse_strings(data)?,
+                });
+            },
+            Ok(Event::Eof) => return Err(XmlError::new(data, reader.buffer_position(), "Element \"localization\" not found".to_string())),
+            Err(e) => return Err(XmlError::new(data, reader.error_position(), e.to_string())),
+            _ => (),
+        }
+        buf.clear();
+    }
+}
+
+// Returns (required attribute, optional attribute, text) of every element with the tag
+fn parse_elements(data: &str, tag: &[u8], attribute: &[u8], optional_attribute: Option<&[u8]>) -> Result<Vec<(String, Option<String>, String)>, XmlError> {
+    let mut reader: Reader<&[u8]> = Reader::from_str(data);
+    reader.config_mut().trim_text(true);
+
+    let mut elements: Vec<(String, Option<String>, String)> = Vec::new();
+    // Elements around the entries, a truncated file leaves some of them open
+    let mut open_elements: Vec<String> = Vec::new();
+    let mut buf: Vec<u8> = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) if e.name().as_ref() == tag => {
+                let position: u64 = reader.buffer_position();
+                let name: String = read_attribute(&e, attribute).map_err(|x| XmlError::new(data, position, x))?;
+                let option: Option<String> = match optional_attribute {
+                    Some(x) => read_attribute(&e, x).ok(),
+                    None => None,
+                };
+                let text: String = read_text(&mut reader, tag).map_err(|x| XmlError::new(data, position, x))?;
+                elements.push((name, option, text));
+            },
+            Ok(Event::Start(e)) => open_elements.push(String::from_utf8_lossy(e.name().as_ref()).into_owned()),
+            Ok(Event::End(_)) => { open_elements.pop(); },
+            Ok(Event::Eof) => match open_elements.last() {
+                Some(x) => return Err(XmlError::new(data, data.len() as u64, format!("Element \"{}\" is not closed", x))),
+                None => break,
+            },
+            Err(e) => return Err(XmlError::new(data, reader.error_position(), e.to_string())),
+            _ => (),
+        }
+        buf.clear();
+    }
+
+    Ok(elements)
+}
+
+// Value has to be plain text followed by the end of its element, markup inside would cut it short
+fn read_text(reader: &mut Reader<&[u8]>, tag: &[u8]) -> Result<String, String> {
+    let mut buf = Vec::new();
+    let text: String = match reader.read_event_into(&mut buf) {
+        Ok(Event::Text(e)) => e.unescape().map_err(|e| e.to_string())?.into_owned(),
+        _ => return Err("Expected text".to_string()),
+    };
+    buf.clear();
+    match reader.read_event_into(&mut buf) {
+        Ok(Event::End(e)) if e.name().as_ref() == tag => Ok(text),
+        Err(e) => Err(e.to_string()),
+        _ => Err(format!("Expected end of element \"{}\"", String::from_utf8_lossy(tag))),
+    }
+}
+
+fn read_attribute(e: &BytesStart, name: &[u8]) -> Result<String, String> {
+    let attr_value_result = e.attributes()
+        .filter_map(|a| a.ok())
+        .filter(|a| a.key.0 == name)
+        .map(|a| a.unescape_value().map_err(|e| e.to_string()))
+        .last().ok_or_else(|| format!("Attribute \"{}\" not found", String::from_utf8_lossy(name)));
+
+    match attr_value_result {
+        Ok(x) => Ok(x?.into_owned()),
+        Err(x) => Err(x),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PluralCategory {
+    One,
+    Few,
+    Many,
+    Other,
+}
+
+impl PluralCategory {
+    // Integer rules of CLDR for the languages the game is translated to, other languages use the English rule
+    pub fn select(locale_code: &str, number: i64) -> Self {
+        let number: i64 = number.abs();
+        match locale_code {
+            "ua" | "uk" => {
+                if number % 10 == 1 && number % 100 != 11 {
+                    Self::One
+                } else if (2..=4).contains(&(number % 10)) && !(12..=14).contains(&(number % 100)) {
+                    Self::Few
+                } else {
+                    Self::Many
+                }
+            },
+            _ => if number == 1 { Self::One } else { Self::Other },
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Self::One => "one",
+            Self::Few => "few",
+            Self::Many => "many",
+            Self::Other => "other",
+        }
+    }
+}
+
+// Replaces placeholders in a message:
+// "{name}" is replaced with the value of the argument,
+// "{name, plural, =0 {no points} one {# point} other {# points}}" picks a variant by the exact
+// number or by its plural category ("other" if the category is absent), "#" stands for the number.
+// Placeholders without an argument are left as they are
+pub fn format_message(message: &str, locale_code: &str, args: &[(&str, &str)]) -> String {
+    let mut result: String = String::new();
+    let mut rest: &str = message;
+
+    while let Some(start) = rest.find('{') {
+        let end: usize = match find_closing_brace(&rest[start..]) {
+            Some(x) => start + x,
+            None => break,
+        };
+        result.push_str(&rest[..start]);
+        match format_placeholder(&rest[start + 1..end], locale_code, args) {
+            Some(value) => result.push_str(&value),
+            None => result.push_str(&rest[start..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+    result.push_str(rest);
+
+    result
+}
+
+fn find_closing_brace(text: &str) -> Option<usize> {
+    let mut depth: usize = 0;
+    for (index, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 1 => return Some(index),
+            '}' => depth = depth.saturating_sub(1),
+            _ => (),
+        }
+    }
+    None
+}
+
+fn format_placeholder(placeholder: &str, locale_code: &str, args: &[(&str, &str)]) -> Option<String> {
+    let mut parts = placeholder.splitn(3, ',').map(|x| x.trim());
+    let name: &str = parts.next()?;
+    let value: &str = args.iter().find(|(key, _)| *key == name).map(|(_, x)| *x)?;
+
+    match (parts.next(), parts.next()) {
+        (None, _) => Some(value.to_string()),
+        (Some("plural"), Some(variants)) => {
+            let number: i64 = value.parse::<i64>().ok()?;
+            let variants: Vec<(&str, &str)> = parse_plural_variants(variants);
+            let exact: String = format!("={}", number);
+            let category: &str = PluralCategory::select(locale_code, number).name();
+
+            let text: &str = variants.iter().find(|(x, _)| *x == exact)
+                .or_else(|| variants.iter().find(|(x, _)| *x == category))
+                .or_else(|| variants.iter().find(|(x, _)| *x == PluralCategory::Other.name()))
+                .map(|(_, x)| *x)?;
+            Some(format_message(&text.replace('#', value), locale_code, args))
+        },
+        _ => None,
+    }
+}
+
+fn parse_plural_variants(text: &str) -> Vec<(&str, &str)> {
+    let mut variants: Vec<(&str, &str)> = Vec::new();
+    let mut rest: &str = text;
+
+    while let Some(start) = rest.find('{') {
+        let end: usize = match find_closing_brace(&rest[start..]) {
+            Some(x) => start + x,
+            None => break,
+        };
+        variants.push((rest[..start].trim(), &rest[start + 1..end]));
+        rest = &rest[end + 1..];
+    }
+
+    variants
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translations(values: &[(&str, &str)]) -> Translations {
+        let mut translations: Translations = Translations::default();
+        for (key, value) in values {
+            translations.insert(key.to_string(), value.to_string());
+        }
+        translations
+    }
+
+    #[test]
+    fn falls_back_to_base_locale_and_key() {
+        let base: Translations = translations(&[("exit_btn", "Exit"), ("start_menu", "Start")]);
+        let mut german: Translations = translations(&[("exit_btn", "Beenden")]);
+        german.set_fallback(&base);
+
+        assert_eq!(german.get("exit_btn"), "Beenden");
+        assert_eq!(german.get("start_menu"), "Start");
+        assert_eq!(german.get("help_menu"), "help_menu");
+    }
+
+    #[test]
+    fn reports_missing_keys() {
+        let base: Translations = translations(&[("exit_btn", "Exit"), ("start_menu", "Start")]);
+        let mut german: Translations = translations(&[("exit_btn", "Beenden")]);
+        german.set_fallback(&base);
+
+        assert_eq!(german.get_missing_keys(&["exit_btn", "start_menu", "help_menu"]), vec!["start_menu", "help_menu"]);
+    }
+
+    #[test]
+    fn parses_locale_strings() {
+        let data: &str = "<localization>\n    <string name=\"exit_btn\">Exit &amp; save</string>\n</localization>";
+        let broken: &str = "<localization>\n    <string name=\"exit_btn\">Exit</strin>\n</localization>";
+
+        assert_eq!(parse_strings(data), Ok(vec![("exit_btn".to_string(), "Exit & save".to_string())]));
+        assert_eq!(parse_strings(broken).map_err(|e| e.line), Err(2));
+
+        let truncated: &str = "<l>\n<string name=\"a\">Вийти</string>\n<string name=\"b\">Ві";
+        let markup: &str = "<l>\n<string name=\"a\">Вийти<b>x</b></string>\n</l>";
+        let unclosed: &str = "<l>\n<string name=\"a\">Вийти</string>\n";
+        assert_eq!(parse_strings(truncated).map_err(|e| e.line), Err(3));
+        assert_eq!(parse_strings(markup).map_err(|e| e.message), Err("Expected end of element \"string\"".to_string()));
+        assert_eq!(parse_strings(unclosed).map_err(|e| e.message), Err("Element \"l\" is not closed".to_string()));
+    }
+
+    #[test]
+    fn parses_optional_language_font() {
+        let data: &str = "<localization>\n    <language code=\"en\">English</language>\n    <language code=\"ja\" font=\"fonts/NotoSansJP.ttf\">日本語</language>\n</localization>";
+        let languages: Vec<LanguageEntry> = parse_language_list(data).unwrap();
+
+        assert_eq!(languages[0].font, None);
+        assert_eq!(languages[1].font, Some("fonts/NotoSansJP.ttf".to_string()));
+        assert_eq!(languages[1].language, "日本語");
+    }
+
+    #[test]
+    fn parses_locale_pack() {
+        let data: &str = "<localization code=\"de-ch\" language=\"Schweizerdeutsch\">\n    <string name=\"exit_btn\">Tschüss</string>\n</localization>";
+        let pack: LocalePack = parse_locale_pack(data).unwrap();
+
+        assert_eq!(pack.code, "de-ch");
+        assert_eq!(pack.language, Some("Schweizerdeutsch".to_string()));
+        assert_eq!(pack.font, None);
+        assert_eq!(pack.strings, vec![("exit_btn".to_string(), "Tschüss".to_string())]);
+        assert!(parse_locale_pack("<localization>\n</localization>").is_err());
+    }
+
+    #[test]
+    fn collects_glyphs_of_translations() {
+        let glyphs: String = collect_glyphs(["Übung", "Крок 3"].into_iter());
+
+        assert!(glyphs.contains('Ü') && glyphs.contains('К') && glyphs.contains('7'));
+        assert_eq!(glyphs.matches('g').count(), 1);
+    }
+
+    #[test]
+    fn replaces_named_placeholders() {
+        let message: &str = "Records: {mode}, {size} {unknown}";
+
+        assert_eq!(format_message(message, "en", &[("size", "8x8"), ("mode", "Countdown")]), "Records: Countdown, 8x8 {unknown}");
+        assert_eq!(format_message("Broken {mode", "en", &[("mode", "Countdown")]), "Broken {mode");
+    }
+
+    #[test]
+    fn selects_plural_forms() {
+        let english: &str = "{points, plural, =0 {no points} one {# point} other {# points}} and {fails} fails";
+        let ukrainian: &str = "{points, plural, one {# бал} few {# бали} many {# балів} other {# бала}}";
+
+        assert_eq!(format_message(english, "en", &[("points", "0"), ("fails", "2")]), "no points and 2 fails");
+        assert_eq!(format_message(english, "en", &[("points", "1"), ("fails", "2")]), "1 point and 2 fails");
+        assert_eq!(format_message(english, "de", &[("points", "21"), ("fails", "2")]), "21 points and 2 fails");
+
+        let forms: Vec<String> = [1, 2, 5, 11, 14, 21, 22, 25, 111].iter()
+            .map(|x| format_message(ukrainian, "ua", &[("points", &x.to_string())]))
+            .collect();
+        assert_eq!(forms, vec!["1 бал", "2 бали", "5 балів", "11 балів", "14 балів", "21 бал", "22 бали", "25 балів", "111 балів"]);
+    }
+
+    #[test]
+    fn falls_back_to_other_plural_form() {
+        let message: &str = "{fails, plural, one {# fail} other {# fails}}";
+
+        assert_eq!(format_message(message, "ua", &[("fails", "3")]), "3 fails");
+        assert_eq!(format_message(message, "ua", &[("fails", "many")]), "{fails, plural, one {# fail} other {# fails}}");
+    }
+}
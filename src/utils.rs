@@ -1,9 +1,11 @@
-use rand::thread_rng;
-use rand::seq::SliceRandom;
 use raylib::prelude::*;
 
 use crate::game;
 
+// Keyboard focus is drawn as a frame, so it can be told apart from the mouse hover
+pub const FOCUS_COLOR: Color = Color::DARKBLUE;
+pub const FOCUS_FRAME_WIDTH: f32 = 6.0;
+
 pub struct Button {
     rec: Rectangle,
     color: Color,
@@ -47,11 +49,4 @@ pub fn draw_text_center(d: &mut RaylibDrawHandle, text: &str, y: f32, font_size:
         y: y
     };
     d.draw_text_ex(game.get_font(), text, text_pos, font_size as f32, game.get_font_spacing(), color);
-}
-
-pub fn generate_numbers_array(length: i32) -> Vec<i32> {
-    let mut vec: Vec<i32> = (1..=length).collect();
-    vec.shuffle(&mut thread_rng());
-
-    vec
 }
\ No newline at end of file
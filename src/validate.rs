@@ -0,0 +1,343 @@
+use std::collections::HashSet;
+use std::path::{ Path, PathBuf };
+
+use crate::consts;
+use crate::paths;
+use crate::translations::{ self, LanguageEntry, LocalePack, XmlError };
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Problem {
+    pub file: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}: {}", self.file, self.message)
+    }
+}
+
+// Checks locale files for translators, returns exit code of the process
+pub fn run() -> i32 {
+    let assets_dir: &Path = paths::get_assets_dir();
+    let mut problems: Vec<Problem> = validate_locales(assets_dir);
+    problems.extend(validate_locale_packs(assets_dir, &paths::get_locale_pack_dirs()));
+
+    for problem in problems.iter() {
+        println!("{}", problem);
+    }
+    if problems.is_empty() {
+        println!("All locales in {} are valid", assets_dir.display());
+        0
+    } else {
+        println!("Found {} problems in {}", problems.len(), assets_dir.display());
+        1
+    }
+}
+
+pub fn validate_locales(assets_dir: &Path) -> Vec<Problem> {
+    let mut problems: Vec<Problem> = Vec::new();
+    let codes_file: String = "locales/codes.xml".to_string();
+
+    let languages: Vec<LanguageEntry> = match read_xml(assets_dir, &codes_file, translations::parse_language_list) {
+        Ok(x) => x,
+        Err(message) => {
+            problems.push(Problem { file: codes_file, message: message });
+            return problems;
+        },
+    };
+
+    // Default font is reported once, and only when some locale is drawn with it
+    let default_font: Option<HashSet<char>> = if languages.iter().any(|x| x.font.is_none()) {
+        read_font_chars(&assets_dir.join(consts::DEFAULT_FONT_PATH), &mut problems, consts::DEFAULT_FONT_PATH)
+    } else {
+        None
+    };
+
+    for entry in languages.iter() {
+        let flag_file: String = format!("images/locales/{}.png", entry.code);
+        if !assets_dir.join(&flag_file).is_file() {
+            problems.push(Problem { file: flag_file, message: format!("flag of {} is missing", entry.language) });
+        }
+        let own_font: Option<HashSet<char>> = match &entry.font {
+            Some(font_file) if !assets_dir.join(font_file).is_file() => {
+                problems.push(Problem { file: font_file.clone(), message: format!("font of {} is missing", entry.language) });
+                None
+            },
+            Some(font_file) => read_font_chars(&assets_dir.join(font_file), &mut problems, font_file),
+            None => None,
+        };
+
+        // Characters are checked only against a font that could be read, the missing one is reported above
+        let font: Option<&HashSet<char>> = if entry.font.is_some() { own_font.as_ref() } else { default_font.as_ref() };
+        let locale_file: String = format!("locales/{}.xml", entry.code);
+        match read_xml(assets_dir, &locale_file, translations::parse_strings) {
+            Ok(strings) => problems.extend(validate_strings(&strings, true, font).into_iter().map(|x| Problem {
+                file: locale_file.clone(),
+                message: x,
+            })),
+            Err(message) => problems.push(Problem { file: locale_file, message: message }),
+        }
+    }
+
+    problems
+}
+
+pub fn validate_locale_packs(assets_dir: &Path, pack_dirs: &[PathBuf]) -> Vec<Problem> {
+    let mut problems: Vec<Problem> = Vec::new();
+    let bundled_languages: Vec<LanguageEntry> = read_xml(assets_dir, "locales/codes.xml", translations::parse_language_list).unwrap_or_default();
+    // Problems of the bundled fonts are reported by validate_locales, so they are only skipped here
+    let mut bundled_fonts: Vec<Problem> = Vec::new();
+    let default_font: Option<HashSet<char>> = read_font_chars(&assets_dir.join(consts::DEFAULT_FONT_PATH), &mut bundled_fonts, consts::DEFAULT_FONT_PATH);
+
+    for dir in pack_dirs.iter() {
+        let pack_file: String = dir.join(paths::LOCALE_PACK_FILE).display().to_string();
+        let pack: LocalePack = match read_xml(dir, paths::LOCALE_PACK_FILE, translations::parse_locale_pack) {
+            Ok(x) => x,
+            Err(message) => {
+                problems.push(Problem { file: pack_file, message: message });
+                continue;
+            },
+        };
+
+        // Pack of a bundled language may override only some of its strings
+        let bundled: Option<&LanguageEntry> = bundled_languages.iter().find(|x| x.code == pack.code);
+        let is_override: bool = bundled.is_some();
+        if !is_override && pack.language.is_none() {
+            problems.push(Problem { file: pack_file.clone(), message: "attribute \"language\" is required for a new locale".to_string() });
+        }
+
+        // Font of the pack replaces the one of the bundled language, which replaces the default font
+        let font: Option<HashSet<char>> = match (&pack.font, bundled.and_then(|x| x.font.as_ref())) {
+            (Some(font_file), _) if !dir.join(font_file).is_file() => {
+                problems.push(Problem { file: dir.join(font_file).display().to_string(), message: format!("font of {} is missing", pack.code) });
+                None
+            },
+            (Some(font_file), _) => read_font_chars(&dir.join(font_file), &mut problems, &dir.join(font_file).display().to_string()),
+            (None, Some(font_file)) => read_font_chars(&assets_dir.join(font_file), &mut bundled_fonts, font_file),
+            (None, None) => default_font.clone(),
+        };
+
+        problems.extend(validate_strings(&pack.strings, !is_override, font.as_ref()).into_iter().map(|x| Problem {
+            file: pack_file.clone(),
+            message: x,
+        }));
+    }
+
+    problems
+}
+
+fn read_xml<T>(assets_dir: &Path, file: &str, parse: fn(&str) -> Result<T, XmlError>) -> Result<T, String> {
+    let data: String = std::fs::read_to_string(assets_dir.join(file)).map_err(|e| e.to_string())?;
+    parse(&data).map_err(|e| e.to_string())
+}
+
+fn read_font_chars(path: &Path, problems: &mut Vec<Problem>, file: &str) -> Option<HashSet<char>> {
+    match std::fs::read(path).map_err(|e| e.to_string()).and_then(|data| parse_font_chars(&data)) {
+        Ok(chars) => Some(chars),
+        Err(message) => {
+            problems.push(Problem { file: file.to_string(), message: format!("unable to read the font: {}", message) });
+            None
+        },
+    }
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, String> {
+    data.get(offset..offset + 2).map(|x| u16::from_be_bytes([x[0], x[1]])).ok_or_else(|| "unexpected end of file".to_string())
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, String> {
+    data.get(offset..offset + 4).map(|x| u32::from_be_bytes([x[0], x[1], x[2], x[3]])).ok_or_else(|| "unexpected end of file".to_string())
+}
+
+// Characters a TrueType or OpenType font has glyphs for, taken from the Unicode subtable of its "cmap" table
+fn parse_font_chars(data: &[u8]) -> Result<HashSet<char>, String> {
+    let mut cmap_offset: Option<usize> = None;
+    for i in 0..read_u16(data, 4)? as usize {
+        let record: usize = 12 + i * 16;
+        if data.get(record..record + 4) == Some(b"cmap".as_slice()) {
+            cmap_offset = Some(read_u32(data, record + 8)? as usize);
+        }
+    }
+    let cmap_offset: usize = cmap_offset.ok_or_else(|| "table \"cmap\" not found".to_string())?;
+
+    let mut subtables: Vec<(u16, usize)> = Vec::new();
+    for i in 0..read_u16(data, cmap_offset + 2)? as usize {
+        let record: usize = cmap_offset + 4 + i * 8;
+        let platform: u16 = read_u16(data, record)?;
+        let encoding: u16 = read_u16(data, record + 2)?;
+        if platform == 0 || (platform == 3 && (encoding == 1 || encoding == 10)) {
+            let offset: usize = cmap_offset + read_u32(data, record + 4)? as usize;
+            subtables.push((read_u16(data, offset)?, offset));
+        }
+    }
+
+    // Glyph 0 is the box drawn for missing characters, so codes mapped to it are not covered.
+    // Subtable of all planes (format 12) is preferred to the one of the basic plane (format 4)
+    let mut chars: HashSet<char> = HashSet::new();
+    if let Some((_, offset)) = subtables.iter().find(|(format, _)| *format == 12) {
+        for i in 0..read_u32(data, offset + 12)? as usize {
+            let group: usize = offset + 16 + i * 12;
+            let first: u32 = read_u32(data, group)?;
+            let last: u32 = read_u32(data, group + 4)?.min(char::MAX as u32);
+            let first_glyph: u32 = read_u32(data, group + 8)?;
+            chars.extend((first..=last).filter(|x| first_glyph.wrapping_add(x - first) != 0).filter_map(char::from_u32));
+        }
+    } else if let Some((_, offset)) = subtables.iter().find(|(format, _)| *format == 4) {
+        let segment_count: usize = read_u16(data, offset + 6)? as usize / 2;
+        let ends: usize = offset + 14;
+        let starts: usize = ends + segment_count * 2 + 2;
+        let deltas: usize = starts + segment_count * 2;
+        let range_offsets: usize = deltas + segment_count * 2;
+        for i in 0..segment_count {
+            let first: u16 = read_u16(data, starts + i * 2)?;
+            let last: u16 = read_u16(data, ends + i * 2)?;
+            let delta: u16 = read_u16(data, deltas + i * 2)?;
+            let range_offset: usize = read_u16(data, range_offsets + i * 2)? as usize;
+            for code in first..=last {
+                // Range offset counts from the place where it is stored
+                let glyph: u16 = match range_offset {
+                    0 => code.wrapping_add(delta),
+                    _ => match read_u16(data, range_offsets + i * 2 + range_offset + (code - first) as usize * 2)? {
+                        0 => 0,
+                        x => x.wrapping_add(delta),
+                    },
+                };
+                if glyph != 0 {
+                    chars.extend(char::from_u32(code as u32));
+                }
+            }
+        }
+    } else {
+        return Err("no Unicode character map".to_string());
+    }
+
+    Ok(chars)
+}
+
+fn validate_strings(strings: &[(String, String)], is_complete: bool, font: Option<&HashSet<char>>) -> Vec<String> {
+    let mut problems: Vec<String> = Vec::new();
+    let keys: HashSet<&str> = strings.iter().map(|(key, _)| key.as_str()).collect();
+
+    if is_complete {
+        for key in consts::ALL_STRING_NAMES.iter().filter(|x| !keys.contains(*x)) {
+            problems.push(format!("missing key \"{}\"", key));
+        }
+    }
+
+    let mut seen_keys: HashSet<&str> = HashSet::new();
+    for (key, value) in strings.iter() {
+        if !consts::ALL_STRING_NAMES.contains(&key.as_str()) {
+            problems.push(format!("unknown key \"{}\"", key));
+        }
+        if !seen_keys.insert(key) {
+            problems.push(format!("duplicate key \"{}\"", key));
+        }
+
+        if let Some(font) = font {
+            let mut glyphs: Vec<char> = Vec::new();
+            for glyph in value.chars().filter(|x| !x.is_control() && !font.contains(x)) {
+                if !glyphs.contains(&glyph) {
+                    glyphs.push(glyph);
+                }
+            }
+            if !glyphs.is_empty() {
+                problems.push(format!("key \"{}\" uses characters missing from the font: {}", key, glyphs.iter().collect::<String>()));
+            }
+        }
+    }
+
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn get_strings(values: &[(&str, &str)]) -> Vec<(String, String)> {
+        values.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect()
+    }
+
+    #[test]
+    fn reports_key_and_glyph_problems() {
+        let mut values: Vec<(&str, &str)> = consts::ALL_STRING_NAMES.iter().map(|x| (*x, "ok")).collect();
+        values.retain(|(key, _)| *key != consts::EXIT_BTN_STRING_NAME);
+        values.push(("colour_menu", "ok"));
+        values.push((consts::START_MENU_STRING_NAME, "Start"));
+        values.push((consts::HELP_MENU_STRING_NAME, "Hilfe 帮助 助"));
+
+        let font: HashSet<char> = consts::FONT_BASE_GLYPHS.chars().collect();
+        let problems: Vec<String> = validate_strings(&get_strings(&values), true, Some(&font));
+
+        assert_eq!(problems, vec![
+            "missing key \"exit_btn\"".to_string(),
+            "unknown key \"colour_menu\"".to_string(),
+            "duplicate key \"start_menu\"".to_string(),
+            "duplicate key \"help_menu\"".to_string(),
+            "key \"help_menu\" uses characters missing from the font: 帮助".to_string(),
+        ]);
+        assert_eq!(validate_strings(&get_strings(&values), true, None).len(), 4);
+        assert_eq!(validate_strings(&get_strings(&values), false, None).len(), 3);
+    }
+
+    #[test]
+    fn reads_characters_of_font() {
+        // Font with only a "cmap" table: "A"-"C" mapped by delta, "a"-"b" through the glyph array where "b" has no glyph
+        let subtable: Vec<u16> = vec![
+            4, 46, 0, 6, 4, 1, 2,
+            0x43, 0x62, 0xFFFF,
+            0,
+            0x41, 0x61, 0xFFFF,
+            1u16.wrapping_sub(0x41), 0, 1,
+            0, 4, 0,
+            5, 0,
+        ];
+        let mut data: Vec<u8> = vec![0, 1, 0, 0, 0, 1, 0, 16, 0, 0, 0, 0];
+        data.extend(b"cmap");
+        data.extend([0, 0, 0, 0, 0, 0, 0, 28, 0, 0, 0, 58]);
+        data.extend([0, 0, 0, 1, 0, 3, 0, 1, 0, 0, 0, 12]);
+        data.extend(subtable.iter().flat_map(|x| x.to_be_bytes()));
+
+        let chars: HashSet<char> = parse_font_chars(&data).unwrap();
+
+        assert_eq!(chars, HashSet::from(['A', 'B', 'C', 'a']));
+        assert_eq!(parse_font_chars(&data[..12]), Err("table \"cmap\" not found".to_string()));
+        assert!(parse_font_chars(&data[..40]).is_err());
+    }
+
+    #[test]
+    fn reports_broken_files_and_missing_flags() {
+        let root: PathBuf = std::env::temp_dir().join(format!("numbers-game-validate-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("locales")).unwrap();
+        std::fs::write(root.join("locales/codes.xml"), "<localization>\n<language code=\"xx\" font=\"fonts/xx.ttf\">Test</language>\n</localization>").unwrap();
+        std::fs::write(root.join("locales/xx.xml"), "<localization>\n<string name=\"exit_btn\">Exit</strin>\n</localization>").unwrap();
+
+        let problems: Vec<Problem> = validate_locales(&root);
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(problems.len(), 3);
+        assert_eq!(problems[0].file, "images/locales/xx.png");
+        assert_eq!(problems[1].file, "fonts/xx.ttf");
+        assert_eq!(problems[2].file, "locales/xx.xml");
+        assert!(problems[2].message.starts_with("line 2, column"));
+    }
+
+    #[test]
+    fn allows_partial_packs_of_bundled_languages() {
+        let root: PathBuf = std::env::temp_dir().join(format!("numbers-game-packs-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("assets/locales")).unwrap();
+        std::fs::create_dir_all(root.join("de-ch")).unwrap();
+        std::fs::create_dir_all(root.join("en-internal")).unwrap();
+        std::fs::write(root.join("assets/locales/codes.xml"), "<localization>\n<language code=\"de\">Deutsch</language>\n</localization>").unwrap();
+        std::fs::write(root.join("de-ch").join(paths::LOCALE_PACK_FILE), "<localization code=\"de\">\n<string name=\"exit_btn\">Tschüss</string>\n</localization>").unwrap();
+        std::fs::write(root.join("en-internal").join(paths::LOCALE_PACK_FILE), "<localization code=\"en-internal\">\n<string name=\"exit_btn\">Exit</string>\n</localization>").unwrap();
+
+        let problems: Vec<Problem> = validate_locale_packs(&root.join("assets"), &[root.join("de-ch"), root.join("en-internal")]);
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(problems.len(), consts::ALL_STRING_NAMES.len());
+        assert!(problems.iter().all(|x| x.file.contains("en-internal")));
+        assert_eq!(problems[0].message, "attribute \"language\" is required for a new locale");
+    }
+}